@@ -26,7 +26,7 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_plugins(PanOrbitCameraPlugin)
         .add_plugins(EguiPlugin)
-        .add_plugins(BevyInfrastructurePlugin)
+        .add_plugins(BevyInfrastructurePlugin::default())
         .add_systems(Startup, setup)
         .add_systems(Update, ui_element)
         .run();
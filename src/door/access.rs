@@ -0,0 +1,79 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to store an access level.
+///
+/// On a door it is the level needed to open the door, and on any other entity the level that
+/// entity holds, like the level of its keycard. A door without one can be opened by anyone, and an
+/// entity without one holds level `0`.
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct AccessLevel(pub u8);
+
+/// A system to refuse door events that would let an entity open a door above its access level.
+///
+/// Only events with a requester, set with `DoorEvent::with_requester`, are checked. Events without
+/// one come from game logic rather than from someone at the door, like schedules or an alarm, and
+/// are trusted. Requests to close a door are always accepted. It runs in
+/// `DoorSystemSet::Validation`, after every door event of the frame has been sent, and
+/// `update_door_goal` skips the refused events.
+///
+/// The requester is kept on the `DoorEvent` itself rather than in a separate access request event,
+/// so every way of addressing a door is checked the same way and a refused request is dropped like
+/// any other refused event. The cost is that the check is opt-in: a request sent without a
+/// requester is never checked.
+pub(crate) fn enforce_access_control(
+    mut door_requests: EventReader<DoorEvent>,
+    mut refused_events: ResMut<RefusedDoorEvents>,
+    door_targets: DoorEventTargets,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    access_queries: Query<&AccessLevel>,
+    children_queries: Query<&Children>,
+    state_queries: Query<&DoorState, With<DoorJoint>>,
+) {
+    for (door_request, event_id) in door_requests.read_with_id() {
+        let Some(requester) = door_request.requester else {
+            continue;
+        };
+        let holder_level = access_queries.get(requester).copied().unwrap_or_default();
+
+        for door_entity in door_targets.resolve(door_request) {
+            // A single leaf is guarded by the access level of its door
+            let root = door_root(door_entity, &leaf_queries);
+            let Ok(required_level) = access_queries.get(root) else {
+                continue;
+            };
+            let states = state_queries.iter_many(children_queries.iter_descendants(root));
+            if holder_level >= *required_level || !door_request.opens(states) {
+                continue;
+            }
+
+            log::warn!(
+                "Entity {:?} with access level {} cannot open a door that needs {}",
+                requester,
+                holder_level.0,
+                required_level.0
+            );
+            if !refused_events.0.contains(&event_id) {
+                refused_events.0.push(event_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to pair two doors into an airlock, so only one of them can be open at a time.
+///
+/// Spawn it on its own entity, naming the two doors. A request to open one door is refused while
+/// the other door is not `Closed`. Requests to close either door are always accepted.
+#[derive(Component, Clone)]
+pub struct AirlockPair {
+    pub door_a: String,
+    pub door_b: String,
+}
+
+impl AirlockPair {
+    /// Create a new airlock pair component.
+    pub fn new(door_a: String, door_b: String) -> Self {
+        AirlockPair { door_a, door_b }
+    }
+}
+
+/// A system to refuse door events that would open both doors of an airlock.
+///
+/// It runs in `DoorSystemSet::Validation`, after every door event of the frame has been sent, and
+/// `update_door_goal` skips the refused events. A door counts as open while any of its joints is
+/// not `Closed` or is already heading open, and once an earlier event of the same frame opened it.
+pub(crate) fn enforce_airlock_interlock(
+    mut door_requests: EventReader<DoorEvent>,
+    mut refused_events: ResMut<RefusedDoorEvents>,
+    registry: Res<DoorRegistry>,
+    door_targets: DoorEventTargets,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    lock_queries: Query<&DoorLock>,
+    airlock_queries: Query<&AirlockPair>,
+    children_queries: Query<&Children>,
+    joint_queries: Query<(&DoorState, &DoorGoal), With<DoorJoint>>,
+) {
+    if airlock_queries.is_empty() {
+        door_requests.clear();
+        return;
+    }
+
+    let door_joints = |door_entity: Entity| {
+        joint_queries.iter_many(children_queries.iter_descendants(door_entity))
+    };
+    let door_closed = |door_entity: Entity| {
+        door_joints(door_entity)
+            .all(|(state, goal)| *state == DoorState::Closed && *goal == DoorGoal::Closed)
+    };
+    // The doors opened by the events accepted so far this frame
+    let mut opened_doors: Vec<Entity> = Vec::new();
+
+    for (door_request, event_id) in door_requests.read_with_id() {
+        if refused_events.0.contains(&event_id) {
+            continue;
+        }
+        let opens = |door_entity: Entity| {
+            !door_locked(door_entity, &leaf_queries, &lock_queries)
+                && door_request.opens(door_joints(door_entity).map(|(state, _)| state))
+        };
+
+        // A request for a single leaf counts as a request for its door
+        let targets: Vec<Entity> = door_targets
+            .resolve(door_request)
+            .into_iter()
+            .map(|door_entity| door_root(door_entity, &leaf_queries))
+            .collect();
+        let opened: Vec<Entity> = targets
+            .iter()
+            .copied()
+            .filter(|door_entity| opens(*door_entity))
+            .collect();
+
+        let mut refused = false;
+        for airlock in airlock_queries.iter() {
+            for (doors, name, other_doors, other_name) in [
+                (
+                    registry.doors(&airlock.door_a),
+                    &airlock.door_a,
+                    registry.doors(&airlock.door_b),
+                    &airlock.door_b,
+                ),
+                (
+                    registry.doors(&airlock.door_b),
+                    &airlock.door_b,
+                    registry.doors(&airlock.door_a),
+                    &airlock.door_a,
+                ),
+            ] {
+                if !doors.iter().any(|door| opened.contains(door)) {
+                    continue;
+                }
+                let other_closed = other_doors.iter().all(|other_door| {
+                    door_closed(*other_door)
+                        && !opened_doors.contains(other_door)
+                        && !opened.contains(other_door)
+                });
+                if other_closed {
+                    continue;
+                }
+
+                log::warn!(
+                    "Door {} cannot open while airlock door {} is open",
+                    name,
+                    other_name
+                );
+                refused = true;
+            }
+        }
+
+        if refused {
+            refused_events.0.push(event_id);
+        } else {
+            opened_doors.extend(opened);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::HashSet;
+
+/// A component to count how many times a door has been opened.
+///
+/// Add it to a door entity next to the `DoorBundle`. The count goes up each time the door leaves
+/// `DoorState::Closed`; a double door counts once for both leaves.
+#[derive(Component, Clone, Copy, Default)]
+pub struct DoorOpenCount(pub u32);
+
+/// A component to measure how long a door has been open.
+///
+/// Add it to a door entity next to the `DoorBundle`. A door is open from the moment it leaves
+/// `DoorState::Closed` until every leaf is closed again. `total_seconds` holds the time of the
+/// finished openings, and `current_open_start` the elapsed time at which the current one began.
+#[derive(Component, Clone, Copy, Default)]
+pub struct DoorOpenDuration {
+    pub total_seconds: f64,
+    pub current_open_start: Option<f64>,
+}
+
+impl DoorOpenDuration {
+    /// The open time so far, including the current opening.
+    pub fn seconds_at(&self, elapsed_seconds: f64) -> f64 {
+        match self.current_open_start {
+            Some(start) => self.total_seconds + (elapsed_seconds - start),
+            None => self.total_seconds,
+        }
+    }
+}
+
+/// A resource summing the door analytics components of every door.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct DoorAnalyticsSummary {
+    /// The number of openings over every door with a `DoorOpenCount`.
+    pub open_count: u64,
+    /// The open time over every door with a `DoorOpenDuration`, including current openings.
+    pub open_seconds: f64,
+}
+
+/// A system to update the door analytics components from door state changes.
+pub(crate) fn update_door_analytics(
+    mut open_doors: Local<HashSet<Entity>>,
+    time: Res<Time>,
+    mut state_changes: EventReader<DoorStateChanged>,
+    mut summary: ResMut<DoorAnalyticsSummary>,
+    children_queries: Query<&Children>,
+    joint_queries: Query<&DoorState, With<DoorJoint>>,
+    mut door_queries: Query<
+        (Option<&mut DoorOpenCount>, Option<&mut DoorOpenDuration>),
+        Or<(With<DoorOpenCount>, With<DoorOpenDuration>)>,
+    >,
+) {
+    let now = time.elapsed_seconds_f64();
+    let mut changed_doors: Vec<Entity> = Vec::new();
+    for state_change in state_changes.read() {
+        if !changed_doors.contains(&state_change.door_entity) {
+            changed_doors.push(state_change.door_entity);
+        }
+    }
+
+    for door_entity in changed_doors {
+        let Ok((count, duration)) = door_queries.get_mut(door_entity) else {
+            continue;
+        };
+        // A double door is only closed once both of its leaves are
+        let closed = joint_queries
+            .iter_many(children_queries.iter_descendants(door_entity))
+            .all(|state| *state == DoorState::Closed);
+
+        if !closed && open_doors.insert(door_entity) {
+            if let Some(mut count) = count {
+                count.0 += 1;
+            }
+            if let Some(mut duration) = duration {
+                duration.current_open_start = Some(now);
+            }
+        } else if closed && open_doors.remove(&door_entity) {
+            if let Some(mut duration) = duration {
+                if let Some(start) = duration.current_open_start.take() {
+                    duration.total_seconds += now - start;
+                }
+            }
+        }
+    }
+
+    let mut open_count = 0;
+    let mut open_seconds = 0.0;
+    for (count, duration) in door_queries.iter() {
+        open_count += count.map_or(0, |count| count.0 as u64);
+        open_seconds += duration.map_or(0.0, |duration| duration.seconds_at(now));
+    }
+    summary.open_count = open_count;
+    summary.open_seconds = open_seconds;
+}
@@ -0,0 +1,105 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to choose how a door eases in and out of its movement.
+///
+/// Add it to a door entity next to the `DoorBundle`, or set it on the `DoorProperties` with
+/// `with_easing`. Doors without either move linearly.
+#[derive(Component, Clone, Copy, PartialEq, Debug, Reflect, Default)]
+#[reflect(Component, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum DoorAnimationCurve {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl DoorAnimationCurve {
+    /// Get the open fraction of a door at the given animation progress, both from `0.0` to `1.0`.
+    pub fn sample(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            DoorAnimationCurve::Linear => t,
+            DoorAnimationCurve::EaseIn => t * t,
+            DoorAnimationCurve::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            DoorAnimationCurve::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t) * (2.0 - 2.0 * t) / 2.0
+                }
+            }
+        }
+    }
+
+    /// Get the animation progress at which the curve reaches the given open fraction.
+    pub(crate) fn progress_at(&self, open_fraction: f32) -> f32 {
+        let open_fraction = open_fraction.clamp(0.0, 1.0);
+        if let DoorAnimationCurve::Linear = self {
+            return open_fraction;
+        }
+
+        // The curves only ever increase, so a bisection finds the progress
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..24 {
+            let middle = (low + high) / 2.0;
+            if self.sample(middle) < open_fraction {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        (low + high) / 2.0
+    }
+}
+
+/// A component to store how far a door is along its animation, from `0.0` to `1.0`.
+///
+/// It is kept on each door joint, and the `DoorAnimationCurve` maps it onto the open fraction.
+#[derive(Component, Clone, Copy, PartialEq, Default)]
+pub struct DoorAnimationProgress(pub(crate) f32);
+
+impl DoorAnimationProgress {
+    /// The normalized animation progress of the door.
+    pub fn progress(&self) -> f32 {
+        self.0
+    }
+}
+
+/// Get the animation curve of a door, from the door entity or the root of a composite door, or
+/// else the easing of its properties.
+pub(crate) fn door_animation_curve(
+    door_entity: Entity,
+    properties: &DoorProperties,
+    leaf_queries: &Query<&Parent, With<DoorLeaf>>,
+    curve_queries: &Query<&DoorAnimationCurve>,
+) -> DoorAnimationCurve {
+    curve_queries
+        .get(door_entity)
+        .or_else(|_| curve_queries.get(door_root(door_entity, leaf_queries)))
+        .copied()
+        .unwrap_or(properties.easing)
+}
@@ -0,0 +1,114 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::time::Duration;
+
+/// A component to close a door automatically after it has been open for a while.
+///
+/// Add it to a door entity next to the `DoorBundle`. The timer starts once the door is fully
+/// open and restarts when the door is asked to open again. It follows virtual time, so it is
+/// paused together with the app.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct DoorAutoClose {
+    pub delay_seconds: f32,
+    pub elapsed: f32,
+}
+
+impl DoorAutoClose {
+    /// Create a new door auto close component.
+    pub fn new(delay_seconds: f32) -> Self {
+        DoorAutoClose {
+            delay_seconds,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Create a new door auto close component from a delay duration.
+    pub fn from_delay(delay: Duration) -> Self {
+        DoorAutoClose::new(delay.as_secs_f32())
+    }
+
+    /// Get the time left before the door closes, for example to show a countdown.
+    pub fn remaining(&self) -> Duration {
+        Duration::from_secs_f32((self.delay_seconds - self.elapsed).max(0.0))
+    }
+}
+
+/// A system to restart the auto close timer of doors that are asked to open again.
+///
+/// Requests are resolved to doors like in `update_door_goal`, and refused requests and requests
+/// to open locked doors leave the timer running.
+pub(crate) fn restart_auto_close(
+    mut door_requests: EventReader<DoorEvent>,
+    refused_events: Res<RefusedDoorEvents>,
+    targets: DoorEventTargets,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    lock_queries: Query<&DoorLock>,
+    mut door_queries: Query<&mut DoorAutoClose>,
+) {
+    for (door_request, event_id) in door_requests.read_with_id() {
+        if !matches!(
+            door_request.action,
+            DoorAction::Open | DoorAction::OpenTo(_)
+        ) || refused_events.0.contains(&event_id)
+        {
+            continue;
+        }
+        for door_entity in targets.resolve(door_request) {
+            if door_locked(door_entity, &leaf_queries, &lock_queries) {
+                continue;
+            }
+            let root = door_root(door_entity, &leaf_queries);
+            if let Ok(mut auto_close) = door_queries.get_mut(root) {
+                auto_close.elapsed = 0.0;
+            }
+        }
+    }
+}
+
+/// A system to close doors that have been open for longer than their auto close delay.
+///
+/// Composite doors count as open once all of their leaves are open.
+pub(crate) fn update_auto_close(
+    time: Res<Time<Virtual>>,
+    mut door_requests: EventWriter<DoorEvent>,
+    children_queries: Query<&Children>,
+    mut door_queries: Query<(Entity, &DoorProperties, &mut DoorAutoClose)>,
+    joint_queries: Query<&DoorState, With<DoorJoint>>,
+) {
+    for (entity, properties, mut auto_close) in door_queries.iter_mut() {
+        let mut states = joint_queries
+            .iter_many(children_queries.iter_descendants(entity))
+            .peekable();
+        let is_open = states.peek().is_some() && states.all(|state| *state == DoorState::Open);
+
+        if !is_open {
+            auto_close.elapsed = 0.0;
+            continue;
+        }
+
+        auto_close.elapsed += time.delta_seconds();
+        if auto_close.elapsed >= auto_close.delay_seconds {
+            log::info!("Auto closing door {}", properties.name);
+            door_requests.send(DoorEvent::close_entity(entity));
+            auto_close.elapsed = 0.0;
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to mark entities that open doors with an `AutoOpen` sensor.
+#[derive(Component)]
+pub struct Actor;
+
+/// The default distance beyond the open radius that actors have to leave before a door closes.
+pub const DEFAULT_AUTO_OPEN_HYSTERESIS: f32 = 0.25;
+
+/// The default number of seconds a door stays open after the last actor has left.
+pub const DEFAULT_AUTO_OPEN_CLOSE_DELAY: f32 = 1.0;
+
+/// A component to open a door when an `Actor` comes near it.
+///
+/// Add it to a door entity next to the `DoorBundle`. The door opens once an actor is within
+/// `radius` of the door, and closes `close_delay` seconds after every actor has moved further
+/// than `close_radius` away. Keep `close_radius` above `radius` so an actor standing on the edge
+/// does not open and close the door over and over.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AutoOpen {
+    pub radius: f32,
+    pub close_radius: f32,
+    pub close_delay: f32,
+    occupied: bool,
+    vacant_seconds: f32,
+}
+
+impl AutoOpen {
+    /// Create a new auto open component.
+    pub fn new(radius: f32) -> Self {
+        AutoOpen {
+            radius,
+            close_radius: radius + DEFAULT_AUTO_OPEN_HYSTERESIS,
+            close_delay: DEFAULT_AUTO_OPEN_CLOSE_DELAY,
+            occupied: false,
+            vacant_seconds: 0.0,
+        }
+    }
+
+    /// Set the distance every actor has to leave before the door closes.
+    pub fn with_close_radius(mut self, close_radius: f32) -> Self {
+        if close_radius < self.radius {
+            log::warn!(
+                "Auto open close radius {} is below the open radius {}, using the open radius",
+                close_radius,
+                self.radius
+            );
+        }
+        self.close_radius = close_radius.max(self.radius);
+        self
+    }
+
+    /// Set the number of seconds the door stays open after the last actor has left.
+    pub fn with_close_delay(mut self, close_delay: f32) -> Self {
+        self.close_delay = close_delay.max(0.0);
+        self
+    }
+}
+
+/// A system to open and close doors as actors come and go.
+///
+/// A door that is still closing when an actor arrives is reversed.
+pub(crate) fn update_auto_open(
+    time: Res<Time>,
+    mut door_requests: EventWriter<DoorEvent>,
+    actor_queries: Query<&GlobalTransform, With<Actor>>,
+    children_queries: Query<&Children>,
+    joint_queries: Query<&DoorState, With<DoorJoint>>,
+    mut door_queries: Query<(Entity, &DoorProperties, &GlobalTransform, &mut AutoOpen)>,
+) {
+    for (entity, properties, transform, mut auto_open) in door_queries.iter_mut() {
+        let door_position = transform.translation();
+        let nearest = actor_queries
+            .iter()
+            .map(|actor| actor.translation().distance(door_position))
+            .fold(f32::INFINITY, f32::min);
+
+        if nearest <= auto_open.radius {
+            auto_open.vacant_seconds = 0.0;
+            let states: Vec<DoorState> = joint_queries
+                .iter_many(children_queries.iter_descendants(entity))
+                .copied()
+                .collect();
+            if states.is_empty() {
+                // The door has not been spawned yet
+                continue;
+            }
+
+            if states.contains(&DoorState::Closing) || states.contains(&DoorState::Obstructed) {
+                door_requests.send(DoorEvent::toggle(properties.name.clone()));
+            } else if !auto_open.occupied {
+                door_requests.send(DoorEvent::open(properties.name.clone()));
+            }
+            auto_open.occupied = true;
+            continue;
+        }
+
+        if !auto_open.occupied {
+            continue;
+        }
+
+        if nearest <= auto_open.close_radius {
+            auto_open.vacant_seconds = 0.0;
+            continue;
+        }
+
+        auto_open.vacant_seconds += time.delta_seconds();
+        if auto_open.vacant_seconds >= auto_open.close_delay {
+            auto_open.occupied = false;
+            auto_open.vacant_seconds = 0.0;
+            door_requests.send(DoorEvent::close(properties.name.clone()));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use bevy::ecs::system::SystemParam;
+use bevy::render::primitives::Aabb;
+
+/// A component to mark an entity that doors cannot move through, like a box left in a doorway.
+///
+/// The obstacle's bounds are the `Aabb` of its mesh, which Bevy computes for every mesh entity.
+/// A door that would run into an obstacle stops in `DoorState::Blocked`, and carries on towards its
+/// goal once the obstacle is moved out of the way.
+#[derive(Component, Clone, Copy, Default)]
+pub struct Obstacle;
+
+/// A system parameter to check door movements against obstacles.
+#[derive(SystemParam)]
+pub(crate) struct DoorObstacles<'w, 's> {
+    obstacle_queries: Query<'w, 's, (&'static Aabb, &'static GlobalTransform), With<Obstacle>>,
+    door_queries: Query<'w, 's, (&'static DoorDimensions, &'static GlobalTransform)>,
+}
+
+impl DoorObstacles<'_, '_> {
+    /// Whether moving a door joint from one transform to another runs its panel into an obstacle.
+    ///
+    /// The transforms are relative to the door entity the joint is parented to.
+    pub(crate) fn blocked(
+        &self,
+        door_entity: Entity,
+        properties: &DoorProperties,
+        from: &Transform,
+        to: &Transform,
+    ) -> bool {
+        if self.obstacle_queries.is_empty() {
+            return false;
+        }
+        let Ok((dimensions, door_transform)) = self.door_queries.get(door_entity) else {
+            return false;
+        };
+        let clearances = door_motion_clearance(properties, dimensions, from, to);
+        let world_to_door = door_transform.affine().inverse();
+
+        self.obstacle_queries
+            .iter()
+            .any(|(aabb, obstacle_transform)| {
+                // Bring the obstacle's bounds into the door's space, growing them to stay aligned
+                let obstacle_to_door = world_to_door * obstacle_transform.affine();
+                let center = obstacle_to_door.transform_point3(Vec3::from(aabb.center));
+                let half_extents = Vec3::from(aabb.half_extents);
+                let extent = obstacle_to_door.matrix3.abs() * half_extents;
+                let obstacle = Aabb::from_min_max(center - extent, center + extent);
+                clearances
+                    .iter()
+                    .any(|clearance| clearance.intersects(&obstacle))
+            })
+    }
+}
+
+/// Get the space a door panel passes through when its joint moves between two transforms.
+fn door_motion_clearance(
+    properties: &DoorProperties,
+    dimensions: &DoorDimensions,
+    from: &Transform,
+    to: &Transform,
+) -> Vec<DoorClearance> {
+    match properties.door_type {
+        DoorType::SingleSwinging
+        | DoorType::DoubleAction
+        | DoorType::Folding
+        | DoorType::BiFold { .. } => {
+            let closed_angle = if properties.hinged_right() {
+                std::f32::consts::PI
+            } else {
+                0.0
+            };
+            let from_angle = closed_angle + from.rotation.to_euler(EulerRot::YXZ).0;
+            let to_angle = closed_angle + to.rotation.to_euler(EulerRot::YXZ).0;
+            swept_clearances(
+                properties,
+                dimensions,
+                to.translation,
+                (from_angle, to_angle),
+            )
+        }
+        _ => {
+            let extent = Vec3::new(dimensions.length, dimensions.height, 0.0);
+            let half_thickness = Vec3::new(0.0, 0.0, dimensions.thickness / 2.0);
+            vec![DoorClearance::Box(Aabb::from_min_max(
+                to.translation - half_thickness,
+                to.translation + extent + half_thickness,
+            ))]
+        }
+    }
+}
@@ -0,0 +1,263 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::fmt;
+
+/// A builder for a `DoorBundle`.
+///
+/// The door properties are set through a `DoorPropertiesBuilder`, so both builders share their
+/// setters. Anything that is not set keeps the value of `DoorBundle::default()`.
+#[derive(Default)]
+pub struct DoorBuilder {
+    properties: DoorPropertiesBuilder,
+    dimensions: DoorDimensions,
+    transform: Transform,
+}
+
+impl DoorBuilder {
+    /// Create a new door builder.
+    pub fn new() -> Self {
+        DoorBuilder::default()
+    }
+
+    /// Set the name the door is addressed by.
+    pub fn name(mut self, name: String) -> Self {
+        self.properties = self.properties.name(name);
+        self
+    }
+
+    /// Set the length, height and thickness of the door.
+    pub fn dimensions(mut self, length: f32, height: f32, thickness: f32) -> Self {
+        self.dimensions.length = length;
+        self.dimensions.height = height;
+        self.dimensions.thickness = thickness;
+        self
+    }
+
+    /// Set the door type.
+    pub fn door_type(mut self, door_type: DoorType) -> Self {
+        self.properties = self.properties.door_type(door_type);
+        self
+    }
+
+    /// Set how far the door opens, in units or radians depending on the door type.
+    pub fn swing_value(mut self, swing_value: f32) -> Self {
+        self.properties = self.properties.swing_value(swing_value);
+        self
+    }
+
+    /// Set the door speed, clamped to `MIN_DOOR_SPEED`.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.properties = self.properties.speed(speed);
+        self
+    }
+
+    /// Set the jamb a swinging door is hinged on.
+    pub fn hinge_side(mut self, hinge_side: HingeSide) -> Self {
+        self.properties = self.properties.hinge_side(hinge_side);
+        self
+    }
+
+    /// Set the side of the wall a swinging door opens to.
+    pub fn swing_direction(mut self, swing_direction: SwingDirection) -> Self {
+        self.properties = self.properties.swing_direction(swing_direction);
+        self
+    }
+
+    /// Set the distance at which the moving door snaps onto its goal.
+    ///
+    /// Negative tolerances are made positive, like `DoorProperties::with_snap_tolerance` does.
+    pub fn snap_tolerance(mut self, snap_tolerance: f32) -> Self {
+        self.properties = self.properties.snap_tolerance(snap_tolerance);
+        self
+    }
+
+    /// Set the offset of the pivot from the hinged jamb of a swinging door.
+    pub fn anchor_offset(mut self, anchor_offset: Vec3) -> Self {
+        self.properties = self.properties.anchor_offset(anchor_offset);
+        self
+    }
+
+    /// Set how the door eases in and out of its movement.
+    pub fn easing(mut self, easing: DoorAnimationCurve) -> Self {
+        self.properties = self.properties.easing(easing);
+        self
+    }
+
+    /// Set where the door is placed.
+    pub fn at(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Build the door bundle.
+    ///
+    /// The properties are not checked, use a `DoorPropertiesBuilder` for that.
+    pub fn build(self) -> DoorBundle {
+        DoorBundle {
+            door_properties: self.properties.build_unchecked(),
+            door_dimensions: self.dimensions,
+            transform: self.transform,
+            ..Default::default()
+        }
+    }
+}
+
+/// An error raised when a `DoorPropertiesBuilder` describes a door that would misbehave.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DoorPropertiesError {
+    /// The door has no name, so it cannot be addressed by one.
+    EmptyName,
+    /// The swing value is zero or not finite, so the door would not move.
+    InvalidSwingValue(f32),
+    /// The swing value is negative on a door that only rises.
+    NegativeSwingValue(f32),
+    /// The sign of the swing value contradicts the swing direction.
+    SwingDirectionMismatch(f32, SwingDirection),
+}
+
+impl fmt::Display for DoorPropertiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DoorPropertiesError::EmptyName => write!(f, "door name is empty"),
+            DoorPropertiesError::InvalidSwingValue(swing_value) => {
+                write!(f, "door swing value {} is zero or not finite", swing_value)
+            }
+            DoorPropertiesError::NegativeSwingValue(swing_value) => {
+                write!(f, "door swing value {} is negative", swing_value)
+            }
+            DoorPropertiesError::SwingDirectionMismatch(swing_value, swing_direction) => write!(
+                f,
+                "door swing value {} contradicts swing direction {:?}",
+                swing_value, swing_direction
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DoorPropertiesError {}
+
+/// A builder for `DoorProperties` that checks them before they are built.
+///
+/// Anything that is not set keeps the value of `DoorProperties::default()`.
+#[derive(Default)]
+pub struct DoorPropertiesBuilder {
+    properties: DoorProperties,
+}
+
+impl DoorPropertiesBuilder {
+    /// Create a new door properties builder.
+    pub fn new() -> Self {
+        DoorPropertiesBuilder::default()
+    }
+
+    /// Set the name the door is addressed by.
+    pub fn name(mut self, name: String) -> Self {
+        self.properties.name = name;
+        self
+    }
+
+    /// Set the door type.
+    pub fn door_type(mut self, door_type: DoorType) -> Self {
+        self.properties.door_type = door_type;
+        self
+    }
+
+    /// Set how far the door opens, in units or radians depending on the door type.
+    pub fn swing_value(mut self, swing_value: f32) -> Self {
+        self.properties.swing_value = swing_value;
+        self
+    }
+
+    /// Set the door speed, clamped to `MIN_DOOR_SPEED`.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.properties = self.properties.with_speed(speed);
+        self
+    }
+
+    /// Set the jamb a swinging door is hinged on.
+    pub fn hinge_side(mut self, hinge_side: HingeSide) -> Self {
+        self.properties.hinge_side = hinge_side;
+        self
+    }
+
+    /// Set the side of the wall a swinging door opens to.
+    pub fn swing_direction(mut self, swing_direction: SwingDirection) -> Self {
+        self.properties.swing_direction = Some(swing_direction);
+        self
+    }
+
+    /// Set the distance at which the moving door snaps onto its goal.
+    pub fn snap_tolerance(mut self, snap_tolerance: f32) -> Self {
+        self.properties = self.properties.with_snap_tolerance(snap_tolerance);
+        self
+    }
+
+    /// Set the offset of the pivot from the hinged jamb of a swinging door.
+    pub fn anchor_offset(mut self, anchor_offset: Vec3) -> Self {
+        self.properties.anchor_offset = anchor_offset;
+        self
+    }
+
+    /// Set how the door eases in and out of its movement.
+    pub fn easing(mut self, easing: DoorAnimationCurve) -> Self {
+        self.properties.easing = easing;
+        self
+    }
+
+    /// Build the door properties, checking the name and the swing value.
+    ///
+    /// Garage doors always rise by their own height, so their swing value is not checked. Roll up
+    /// doors must rise, and the sign of the swing value of a swinging door must agree with its
+    /// swing direction if one is set.
+    pub fn build(self) -> Result<DoorProperties, DoorPropertiesError> {
+        let properties = &self.properties;
+        if properties.name.is_empty() {
+            return Err(DoorPropertiesError::EmptyName);
+        }
+
+        let swing_value = properties.swing_value;
+        match properties.door_type {
+            DoorType::Garage { .. } => {}
+            _ if swing_value == 0.0 || !swing_value.is_finite() => {
+                return Err(DoorPropertiesError::InvalidSwingValue(swing_value));
+            }
+            DoorType::RollUp if swing_value < 0.0 => {
+                return Err(DoorPropertiesError::NegativeSwingValue(swing_value));
+            }
+            DoorType::SingleSwinging | DoorType::DoubleSwinging => {
+                if let Some(swing_direction) = properties.swing_direction {
+                    if swing_value.signum() != swing_direction.signum() {
+                        return Err(DoorPropertiesError::SwingDirectionMismatch(
+                            swing_value,
+                            swing_direction,
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(self.properties)
+    }
+
+    /// Build the door properties without checking them, for example in tests.
+    pub fn build_unchecked(self) -> DoorProperties {
+        self.properties
+    }
+}
@@ -0,0 +1,133 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use bevy::ecs::system::SystemId;
+
+/// A callback waiting for a door to finish opening or closing.
+struct DoorCallback {
+    name: String,
+    state: DoorState,
+    system: SystemId,
+}
+
+/// A resource to hold the callbacks registered with `DoorCallbackCommands`.
+#[derive(Resource, Default)]
+pub struct DoorCallbacks {
+    callbacks: Vec<DoorCallback>,
+}
+
+impl DoorCallbacks {
+    /// Get the number of callbacks that have not fired yet.
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Whether every callback has fired.
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+}
+
+/// Commands to run a system once when a door finishes opening or closing.
+///
+/// The system is registered as a one-shot system, runs the first time the named door reaches the
+/// `Open` or `Closed` state after the callback is added, and is then removed. Add the callback
+/// again to run it on the next completion too.
+///
+/// ```ignore
+/// commands.on_door_open("vault_door", spawn_reward);
+/// ```
+pub trait DoorCallbackCommands {
+    /// Run the system once when the named door finishes opening.
+    fn on_door_open<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + Send + 'static,
+    );
+
+    /// Run the system once when the named door finishes closing.
+    fn on_door_close<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + Send + 'static,
+    );
+}
+
+impl DoorCallbackCommands for Commands<'_, '_> {
+    fn on_door_open<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + Send + 'static,
+    ) {
+        add_door_callback(self, name.into(), DoorState::Open, system);
+    }
+
+    fn on_door_close<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + Send + 'static,
+    ) {
+        add_door_callback(self, name.into(), DoorState::Closed, system);
+    }
+}
+
+fn add_door_callback<M>(
+    commands: &mut Commands,
+    name: String,
+    state: DoorState,
+    system: impl IntoSystem<(), (), M> + Send + 'static,
+) {
+    commands.add(move |world: &mut World| {
+        let system = world.register_system(system);
+        world
+            .get_resource_or_insert_with(DoorCallbacks::default)
+            .callbacks
+            .push(DoorCallback {
+                name,
+                state,
+                system,
+            });
+    });
+}
+
+/// A system to run the callbacks of doors that finished opening or closing.
+///
+/// Each callback runs once and its one-shot system is removed afterwards.
+pub(crate) fn run_door_callbacks(
+    mut commands: Commands,
+    mut state_changes: EventReader<DoorStateChanged>,
+    mut callbacks: ResMut<DoorCallbacks>,
+) {
+    for state_change in state_changes.read() {
+        callbacks.callbacks.retain(|callback| {
+            if callback.name != state_change.name || callback.state != state_change.new_state {
+                return true;
+            }
+
+            let system = callback.system;
+            commands.run_system(system);
+            commands.add(move |world: &mut World| {
+                if let Err(error) = world.remove_system(system) {
+                    log::warn!("Door callback could not be removed: {}", error);
+                }
+            });
+            false
+        });
+    }
+}
@@ -0,0 +1,233 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use bevy::render::primitives::Aabb;
+
+/// The volume swept by a swinging door panel about its hinge.
+///
+/// The angles are about the door's Y axis, measured like a `Quat::from_rotation_y` rotation from
+/// the door's length, and run from the closed panel to where it is now.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoorSweptVolume {
+    pub center: Vec3,
+    pub radius: f32,
+    pub angle_range: (f32, f32),
+}
+
+/// The clearance of a single door joint.
+#[derive(Clone, Copy, Debug)]
+pub enum DoorClearance {
+    /// The sector swept by a swinging, folding or revolving door.
+    Swept(DoorSweptVolume),
+    /// The box covered by a sliding or rising door between its closed position and where it is now.
+    Box(Aabb),
+}
+
+/// A component holding the space a door needs to open, so planners can keep out of its way.
+///
+/// Add it to a door entity next to the `DoorBundle`. It is updated every frame with one clearance
+/// per door joint, so a double door has one for each leaf, and a swinging door whose pivot is set
+/// in from its hinged edge has a second one for the part of the panel behind the pivot. The
+/// clearances are in the door's local space, with the door's transform at its lower corner.
+#[derive(Component, Clone, Default, Debug)]
+pub struct DoorClearanceBox {
+    pub clearances: Vec<DoorClearance>,
+}
+
+/// A system to update the clearance of doors from their dimensions and current position.
+pub(crate) fn update_door_clearance(
+    children_queries: Query<&Children>,
+    leaf_queries: Query<&Transform, With<DoorLeaf>>,
+    door_property_queries: Query<(&DoorProperties, &DoorDimensions)>,
+    joint_queries: Query<(&Parent, &Transform), With<DoorJoint>>,
+    mut clearance_queries: Query<(Entity, &mut DoorClearanceBox)>,
+) {
+    for (entity, mut clearance_box) in clearance_queries.iter_mut() {
+        clearance_box.clearances.clear();
+
+        for (parent, joint_transform) in
+            joint_queries.iter_many(children_queries.iter_descendants(entity))
+        {
+            let Ok((properties, dimensions)) = door_property_queries.get(parent.get()) else {
+                continue;
+            };
+            // The joints of composite doors sit on a leaf placed within the door
+            let leaf_transform = leaf_queries.get(parent.get()).copied().unwrap_or_default();
+
+            clearance_box.clearances.extend(door_clearance(
+                properties,
+                dimensions,
+                &leaf_transform,
+                joint_transform,
+            ));
+        }
+    }
+}
+
+/// Get the clearances of a door joint in the door's space, given the transform of its leaf within
+/// the door and its own transform within the leaf.
+fn door_clearance(
+    properties: &DoorProperties,
+    dimensions: &DoorDimensions,
+    leaf_transform: &Transform,
+    joint_transform: &Transform,
+) -> Vec<DoorClearance> {
+    let joint_position = leaf_transform.transform_point(joint_transform.translation);
+
+    match properties.door_type {
+        DoorType::Revolving { .. } => vec![DoorClearance::Swept(DoorSweptVolume {
+            center: joint_position,
+            radius: dimensions.length / 2.0,
+            angle_range: (0.0, std::f32::consts::TAU),
+        })],
+        DoorType::SingleSwinging
+        | DoorType::DoubleAction
+        | DoorType::Folding
+        | DoorType::BiFold { .. } => {
+            let mut closed_angle = leaf_transform.rotation.to_euler(EulerRot::YXZ).0;
+            if properties.hinged_right() {
+                closed_angle += std::f32::consts::PI;
+            }
+            let open_angle = closed_angle + joint_transform.rotation.to_euler(EulerRot::YXZ).0;
+            swept_clearances(
+                properties,
+                dimensions,
+                joint_position,
+                (closed_angle, open_angle),
+            )
+        }
+        _ => {
+            // The panel runs along the length from the joint, and covers its closed position too
+            let closed = leaf_transform.translation;
+            let current = joint_position;
+            let extent = Vec3::new(dimensions.length, dimensions.height, 0.0);
+            let half_thickness = Vec3::new(0.0, 0.0, dimensions.thickness / 2.0);
+            vec![DoorClearance::Box(Aabb::from_min_max(
+                closed.min(current) - half_thickness,
+                closed.max(current) + extent + half_thickness,
+            ))]
+        }
+    }
+}
+
+/// Get the sectors swept by a swinging door panel turning about its pivot between two angles.
+///
+/// A pivot set in from the hinged edge splits the panel in two, and the part behind the pivot
+/// sweeps the opposite sector.
+pub(crate) fn swept_clearances(
+    properties: &DoorProperties,
+    dimensions: &DoorDimensions,
+    pivot: Vec3,
+    (from_angle, to_angle): (f32, f32),
+) -> Vec<DoorClearance> {
+    let inset = properties.pivot_inset();
+    let angle_range = (from_angle.min(to_angle), from_angle.max(to_angle));
+    let behind_range = (
+        angle_range.0 + std::f32::consts::PI,
+        angle_range.1 + std::f32::consts::PI,
+    );
+
+    [
+        (dimensions.length - inset, angle_range),
+        (inset, behind_range),
+    ]
+    .into_iter()
+    .filter(|(radius, _)| *radius > 0.0)
+    .map(|(radius, angle_range)| {
+        DoorClearance::Swept(DoorSweptVolume {
+            center: pivot,
+            radius,
+            angle_range,
+        })
+    })
+    .collect()
+}
+
+/// The largest angle between the rays sampled across a swept volume by `DoorClearance::intersects`.
+const SWEPT_SAMPLE_ANGLE: f32 = 0.05;
+
+impl DoorClearance {
+    /// Whether the clearance overlaps a box in the same space.
+    ///
+    /// Swept volumes are tested in plan view, over the door's whole height.
+    pub fn intersects(&self, aabb: &Aabb) -> bool {
+        let min = Vec3::from(aabb.min());
+        let max = Vec3::from(aabb.max());
+        match self {
+            DoorClearance::Box(clearance) => {
+                let clearance_min = Vec3::from(clearance.min());
+                let clearance_max = Vec3::from(clearance.max());
+                clearance_min.cmple(max).all() && min.cmple(clearance_max).all()
+            }
+            DoorClearance::Swept(volume) => volume.intersects_rect(min.xz(), max.xz()),
+        }
+    }
+}
+
+impl DoorSweptVolume {
+    /// Whether the swept sector overlaps a rectangle in the XZ plane.
+    fn intersects_rect(&self, min: Vec2, max: Vec2) -> bool {
+        let center = self.center.xz();
+        let (start, end) = self.angle_range;
+        let direction = |angle: f32| Vec2::new(angle.cos(), -angle.sin());
+
+        // A rectangle corner inside the sector means the sector reaches into the rectangle
+        let corner_inside = [min, max, Vec2::new(min.x, max.y), Vec2::new(max.x, min.y)]
+            .iter()
+            .any(|corner| {
+                let offset = *corner - center;
+                let angle = (-offset.y).atan2(offset.x);
+                offset.length() <= self.radius
+                    && (angle - start).rem_euclid(std::f32::consts::TAU) <= end - start
+            });
+        if corner_inside {
+            return true;
+        }
+
+        // Otherwise the rectangle can only be crossed by the rays swept through the sector
+        let samples = ((end - start) / SWEPT_SAMPLE_ANGLE).ceil().max(1.0) as u32;
+        (0..=samples).any(|sample| {
+            let angle = start + (end - start) * sample as f32 / samples as f32;
+            segment_intersects_rect(center, center + direction(angle) * self.radius, min, max)
+        })
+    }
+}
+
+/// Whether a line segment crosses a rectangle.
+fn segment_intersects_rect(start: Vec2, end: Vec2, min: Vec2, max: Vec2) -> bool {
+    let delta = end - start;
+    let mut near = 0.0_f32;
+    let mut far = 1.0_f32;
+    for axis in 0..2 {
+        if delta[axis].abs() < f32::EPSILON {
+            if start[axis] < min[axis] || start[axis] > max[axis] {
+                return false;
+            }
+            continue;
+        }
+        let t1 = (min[axis] - start[axis]) / delta[axis];
+        let t2 = (max[axis] - start[axis]) / delta[axis];
+        near = near.max(t1.min(t2));
+        far = far.min(t1.max(t2));
+        if near > far {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,148 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::any::TypeId;
+
+/// A Bevy event to duplicate an existing door under a new name.
+#[derive(Event)]
+pub struct DoorCloneEvent {
+    pub source_name: String,
+    pub target_name: String,
+    pub target_transform: Transform,
+}
+
+impl DoorCloneEvent {
+    pub fn new(source_name: String, target_name: String, target_transform: Transform) -> Self {
+        DoorCloneEvent {
+            source_name,
+            target_name,
+            target_transform,
+        }
+    }
+}
+
+/// A system to clone doors based on the door clone event.
+///
+/// The source door is looked up in the `DoorRegistry`, and every reflected component registered in
+/// the `AppTypeRegistry` is copied from it onto a new entity, apart from its name, transform and
+/// hierarchy, along with the door components of this crate that are not reflected. The joints,
+/// panels and leaves of the clone are rebuilt by `spawn_door`, so the clone always starts closed.
+/// Register your own door components with `App::register_type` and `#[reflect(Component)]` to
+/// have them cloned too.
+pub(crate) fn clone_door(
+    mut commands: Commands,
+    mut clone_requests: EventReader<DoorCloneEvent>,
+    registry: Res<DoorRegistry>,
+    door_queries: Query<&DoorProperties, Without<DoorLeaf>>,
+) {
+    for clone_request in clone_requests.read() {
+        let Some((source, properties)) = registry
+            .doors(&clone_request.source_name)
+            .first()
+            .and_then(|source| Some((*source, door_queries.get(*source).ok()?)))
+        else {
+            log::warn!("Door {} not found for cloning", clone_request.source_name);
+            continue;
+        };
+
+        let mut door_properties = properties.clone();
+        door_properties.name = clone_request.target_name.clone();
+
+        let target = commands
+            .spawn(DoorBundle {
+                door_properties,
+                transform: clone_request.target_transform,
+                ..Default::default()
+            })
+            .id();
+        commands.add(move |world: &mut World| copy_door_components(world, source, target));
+
+        log::info!(
+            "Cloned door {} as {}",
+            clone_request.source_name,
+            clone_request.target_name
+        );
+    }
+}
+
+/// Copy the components of a door onto another entity, apart from the ones that make it a separate
+/// door.
+fn copy_door_components(world: &mut World, source: Entity, target: Entity) {
+    let skipped = [
+        TypeId::of::<DoorProperties>(),
+        TypeId::of::<Transform>(),
+        TypeId::of::<GlobalTransform>(),
+        TypeId::of::<Parent>(),
+        TypeId::of::<Children>(),
+        TypeId::of::<Name>(),
+    ];
+
+    copy_component::<DoorGlass>(world, source, target);
+    copy_component::<DoorMaterial>(world, source, target);
+    copy_component::<DoorMeshOverride>(world, source, target);
+    copy_component::<ProximityDoorTrigger>(world, source, target);
+    copy_component::<DoorSensor>(world, source, target);
+    copy_component::<DoorSchedule>(world, source, target);
+    copy_component::<DoorSignal>(world, source, target);
+    copy_component::<DoorObstruction>(world, source, target);
+    copy_component::<DoorSizeConstraint>(world, source, target);
+    copy_component::<DoorHighlight>(world, source, target);
+    copy_component::<DoorPaused>(world, source, target);
+    copy_component::<DoorOpenCount>(world, source, target);
+    copy_component::<DoorOpenDuration>(world, source, target);
+    copy_component::<DoorClearanceBox>(world, source, target);
+    copy_component::<AirlockPair>(world, source, target);
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let Some(source) = world.get_entity(source) else {
+        return;
+    };
+    let components: Vec<(ReflectComponent, Box<dyn Reflect>)> = source
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            if skipped.contains(&type_id) {
+                return None;
+            }
+            let reflect_component = type_registry.get(type_id)?.data::<ReflectComponent>()?;
+            let component = reflect_component.reflect(source)?.clone_value();
+            Some((reflect_component.clone(), component))
+        })
+        .collect();
+
+    let Some(mut target) = world.get_entity_mut(target) else {
+        return;
+    };
+    for (reflect_component, component) in components {
+        reflect_component.insert(&mut target, component.as_ref(), &type_registry);
+    }
+}
+
+/// Copy a component that is not reflected from one entity onto another, if the source has it.
+fn copy_component<T: Component + Clone>(world: &mut World, source: Entity, target: Entity) {
+    let Some(component) = world.get::<T>(source).cloned() else {
+        return;
+    };
+    if let Some(mut target) = world.get_entity_mut(target) {
+        target.insert(component);
+    }
+}
@@ -0,0 +1,179 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::fmt;
+
+/// A plain data description of a door, mirroring the fields of `DoorBundle`.
+///
+/// It holds no Bevy types, so it can be saved and restored by level editors. With the `serde`
+/// feature it implements `Serialize` and `Deserialize`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct DoorConfig {
+    pub name: String,
+    pub swing_value: f32,
+    pub door_type: DoorType,
+    /// The door speed, or `None` for the `default_speed` of the `DoorPluginConfig`.
+    pub speed: Option<f32>,
+    pub hinge_side: HingeSide,
+    pub swing_direction: Option<SwingDirection>,
+    pub snap_tolerance: f32,
+    pub anchor_offset: [f32; 3],
+    pub easing: DoorAnimationCurve,
+    pub length: f32,
+    pub height: f32,
+    pub thickness: f32,
+    pub frame_thickness: f32,
+    pub frame_depth: f32,
+    pub locked: bool,
+    pub lock_reason: String,
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+impl Default for DoorConfig {
+    fn default() -> Self {
+        let properties = DoorProperties::default();
+        DoorConfig {
+            name: properties.name,
+            swing_value: properties.swing_value,
+            door_type: properties.door_type,
+            speed: properties.speed,
+            hinge_side: properties.hinge_side,
+            swing_direction: properties.swing_direction,
+            snap_tolerance: properties.snap_tolerance,
+            anchor_offset: properties.anchor_offset.to_array(),
+            easing: properties.easing,
+            length: 0.0,
+            height: 0.0,
+            thickness: 0.0,
+            frame_thickness: 0.0,
+            frame_depth: 0.0,
+            locked: false,
+            lock_reason: String::new(),
+            translation: Vec3::ZERO.to_array(),
+            rotation: Quat::IDENTITY.to_array(),
+            scale: Vec3::ONE.to_array(),
+        }
+    }
+}
+
+/// An error raised when a door bundle cannot be described by a `DoorConfig`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DoorConfigError {
+    /// The named field is not finite, so it would not survive being saved.
+    NonFinite(&'static str),
+}
+
+impl fmt::Display for DoorConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DoorConfigError::NonFinite(field) => write!(f, "door {} is not finite", field),
+        }
+    }
+}
+
+impl std::error::Error for DoorConfigError {}
+
+impl From<DoorConfig> for DoorBundle {
+    fn from(config: DoorConfig) -> Self {
+        let mut door_properties =
+            DoorProperties::new(config.name, config.swing_value, config.door_type)
+                .with_hinge_side(config.hinge_side)
+                .with_snap_tolerance(config.snap_tolerance)
+                .with_anchor_offset(Vec3::from_array(config.anchor_offset))
+                .with_easing(config.easing);
+        door_properties.swing_direction = config.swing_direction;
+        if let Some(speed) = config.speed {
+            door_properties = door_properties.with_speed(speed);
+        }
+
+        DoorBundle {
+            door_properties,
+            door_dimensions: DoorDimensions::new(config.length, config.height, config.thickness)
+                .with_frame(config.frame_thickness, config.frame_depth),
+            door_lock: DoorLock {
+                locked: config.locked,
+                reason: config.lock_reason,
+            },
+            transform: Transform {
+                translation: Vec3::from_array(config.translation),
+                rotation: Quat::from_array(config.rotation),
+                scale: Vec3::from_array(config.scale),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<DoorBundle> for DoorConfig {
+    type Error = DoorConfigError;
+
+    fn try_from(bundle: DoorBundle) -> Result<Self, Self::Error> {
+        let properties = bundle.door_properties;
+        let dimensions = bundle.door_dimensions;
+        let transform = bundle.transform;
+
+        for (field, finite) in [
+            ("swing value", properties.swing_value.is_finite()),
+            ("speed", properties.speed().is_finite()),
+            ("snap tolerance", properties.snap_tolerance.is_finite()),
+            ("anchor offset", properties.anchor_offset.is_finite()),
+            ("length", dimensions.length.is_finite()),
+            ("height", dimensions.height.is_finite()),
+            ("thickness", dimensions.thickness.is_finite()),
+            ("frame thickness", dimensions.frame_thickness.is_finite()),
+            ("frame depth", dimensions.frame_depth.is_finite()),
+            ("translation", transform.translation.is_finite()),
+            ("rotation", transform.rotation.is_finite()),
+            ("scale", transform.scale.is_finite()),
+        ] {
+            if !finite {
+                return Err(DoorConfigError::NonFinite(field));
+            }
+        }
+
+        Ok(DoorConfig {
+            name: properties.name,
+            swing_value: properties.swing_value,
+            door_type: properties.door_type,
+            speed: properties.speed,
+            hinge_side: properties.hinge_side,
+            swing_direction: properties.swing_direction,
+            snap_tolerance: properties.snap_tolerance,
+            anchor_offset: properties.anchor_offset.to_array(),
+            easing: properties.easing,
+            length: dimensions.length,
+            height: dimensions.height,
+            thickness: dimensions.thickness,
+            frame_thickness: dimensions.frame_thickness,
+            frame_depth: dimensions.frame_depth,
+            locked: bundle.door_lock.locked,
+            lock_reason: bundle.door_lock.reason,
+            translation: transform.translation.to_array(),
+            rotation: transform.rotation.to_array(),
+            scale: transform.scale.to_array(),
+        })
+    }
+}
@@ -0,0 +1,82 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to limit the size of a door.
+///
+/// Add it to a door entity next to the `DoorBundle`. Doors are checked once, after startup.
+#[derive(Component, Clone)]
+pub struct DoorSizeConstraint {
+    pub min_length: f32,
+    pub max_length: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+}
+
+impl DoorSizeConstraint {
+    /// Create a new door size constraint component.
+    pub fn new(min_length: f32, max_length: f32, min_height: f32, max_height: f32) -> Self {
+        DoorSizeConstraint {
+            min_length,
+            max_length,
+            min_height,
+            max_height,
+        }
+    }
+}
+
+/// A resource to store whether door constraint violations panic.
+#[derive(Resource, Default)]
+pub(crate) struct DoorStrictMode(pub(crate) bool);
+
+/// A system to check door dimensions against their size constraints.
+///
+/// Violations are logged as warnings, or as errors in strict mode. Strict mode also panics in
+/// debug builds.
+pub(crate) fn validate_door_constraints(
+    strict_mode: Res<DoorStrictMode>,
+    queries: Query<(&DoorProperties, &DoorDimensions, &DoorSizeConstraint)>,
+) {
+    for (properties, dimensions, constraint) in queries.iter() {
+        let mut violations = Vec::new();
+        if dimensions.length < constraint.min_length || dimensions.length > constraint.max_length {
+            violations.push(format!(
+                "length {} is outside {}..={}",
+                dimensions.length, constraint.min_length, constraint.max_length
+            ));
+        }
+        if dimensions.height < constraint.min_height || dimensions.height > constraint.max_height {
+            violations.push(format!(
+                "height {} is outside {}..={}",
+                dimensions.height, constraint.min_height, constraint.max_height
+            ));
+        }
+
+        for violation in violations {
+            if !strict_mode.0 {
+                log::warn!("Door {} {}", properties.name, violation);
+                continue;
+            }
+            log::error!("Door {} {}", properties.name, violation);
+            if cfg!(debug_assertions) {
+                panic!("Door {} {}", properties.name, violation);
+            }
+        }
+    }
+}
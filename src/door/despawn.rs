@@ -0,0 +1,63 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A Bevy event to despawn a door and everything spawned for it.
+#[derive(Event)]
+pub struct DespawnDoorEvent {
+    pub name: String,
+}
+
+impl DespawnDoorEvent {
+    pub fn new(name: String) -> Self {
+        DespawnDoorEvent { name }
+    }
+}
+
+/// A system to despawn doors based on the despawn door event.
+///
+/// Every door entity with the name is despawned along with its leaves, joints, panels and frame.
+/// The doors are looked up in the `DoorRegistry`, or found by their name without one.
+pub(crate) fn despawn_door(
+    mut commands: Commands,
+    mut despawn_requests: EventReader<DespawnDoorEvent>,
+    registry: Option<Res<DoorRegistry>>,
+    door_queries: Query<(Entity, &DoorProperties), Without<DoorLeaf>>,
+) {
+    for despawn_request in despawn_requests.read() {
+        let doors: Vec<Entity> = match &registry {
+            Some(registry) => registry.doors(&despawn_request.name).to_vec(),
+            None => door_queries
+                .iter()
+                .filter(|(_, properties)| properties.name == despawn_request.name)
+                .map(|(entity, _)| entity)
+                .collect(),
+        };
+        let despawned = doors.len();
+        for entity in doors {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        if despawned == 0 {
+            log::warn!("Door {} not found for despawning", despawn_request.name);
+        } else {
+            log::info!("Despawned door {}", despawn_request.name);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// The default number of entries kept by a door event log.
+pub const DEFAULT_DOOR_EVENT_LOG_CAPACITY: usize = 32;
+
+/// A enum to describe an entry in a door event log.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DoorEventKind {
+    /// A door event was received for this door.
+    Command(DoorGoal),
+    /// The door moved into a new state.
+    StateChanged(DoorState),
+}
+
+/// A component to record the recent commands and state changes of a door.
+///
+/// Add it to a door entity next to the `DoorBundle`. Each entry is stamped with the elapsed
+/// app time, and the oldest entries are dropped once the log reaches its capacity.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct DoorEventLog {
+    #[reflect(ignore)]
+    pub entries: VecDeque<(Duration, DoorEventKind)>,
+    capacity: usize,
+}
+
+impl Default for DoorEventLog {
+    fn default() -> Self {
+        DoorEventLog::new(DEFAULT_DOOR_EVENT_LOG_CAPACITY)
+    }
+}
+
+impl DoorEventLog {
+    /// Create a new door event log keeping at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        DoorEventLog {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// The maximum number of entries kept by the log.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Record an entry, dropping the oldest entries beyond the capacity.
+    pub fn push(&mut self, time: Duration, kind: DoorEventKind) {
+        self.entries.push_back((time, kind));
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A floor of a `FloorPlan`, with the doors to spawn on it.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Floor {
+    pub index: usize,
+    /// The height of the floor, added to the height of each of its doors.
+    pub y: f32,
+    pub doors: Vec<DoorConfig>,
+}
+
+/// A resource to describe the doors of a building, floor by floor.
+///
+/// Insert it before the app starts and the plugin spawns every door in it on startup.
+#[derive(Resource, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct FloorPlan {
+    pub floors: Vec<Floor>,
+}
+
+impl FloorPlan {
+    /// Get the floor with the given index.
+    pub fn floor(&self, index: usize) -> Option<&Floor> {
+        self.floors.iter().find(|floor| floor.index == index)
+    }
+}
+
+/// A builder for a `FloorPlan`.
+///
+/// Doors are added to the floor added last.
+#[derive(Default)]
+pub struct FloorPlanBuilder {
+    floors: Vec<Floor>,
+}
+
+impl FloorPlanBuilder {
+    /// Create a new floor plan builder.
+    pub fn new() -> Self {
+        FloorPlanBuilder::default()
+    }
+
+    /// Add a floor at the given height.
+    pub fn add_floor(mut self, index: usize, y: f32) -> Self {
+        self.floors.push(Floor {
+            index,
+            y,
+            doors: Vec::new(),
+        });
+        self
+    }
+
+    /// Add a door to the floor added last, positioned relative to the floor.
+    pub fn add_door(mut self, name: String, config: DoorConfig) -> Self {
+        let Some(floor) = self.floors.last_mut() else {
+            log::warn!("Door {} added before any floor, ignoring it", name);
+            return self;
+        };
+        floor.doors.push(DoorConfig { name, ..config });
+        self
+    }
+
+    /// Build the floor plan.
+    pub fn build(self) -> FloorPlan {
+        FloorPlan {
+            floors: self.floors,
+        }
+    }
+}
+
+/// A system to spawn the doors of the floor plan on startup.
+pub(crate) fn load_floor_plan(mut commands: Commands, floor_plan: Option<Res<FloorPlan>>) {
+    let Some(floor_plan) = floor_plan else {
+        return;
+    };
+
+    for floor in floor_plan.floors.iter() {
+        for config in floor.doors.iter() {
+            let mut door_bundle = DoorBundle::from(config.clone());
+            door_bundle.transform.translation.y += floor.y;
+            commands.spawn(door_bundle);
+        }
+        log::info!(
+            "Spawned {} doors on floor {}",
+            floor.doors.len(),
+            floor.index
+        );
+    }
+}
@@ -0,0 +1,95 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to mark the hinge between the two panels of a folding door.
+///
+/// The hinge folds back twice as far as the door joint swings, so the free edge of the door
+/// slides along the track towards the jamb.
+#[derive(Component)]
+pub struct BiFoldJoint;
+
+/// Spawn the two hinged panels of a folding door under its door joint.
+pub(crate) fn spawn_folding_panels(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    dimensions: &DoorDimensions,
+    joint: Entity,
+) {
+    let panel_length = dimensions.length / 2.0;
+    let panel_mesh = meshes.add(Cuboid::new(
+        panel_length,
+        dimensions.height,
+        dimensions.thickness,
+    ));
+    let panel_transform = Transform::from_xyz(panel_length / 2.0, dimensions.height / 2.0, 0.0);
+
+    let first_panel = commands
+        .spawn(PbrBundle {
+            mesh: panel_mesh.clone(),
+            material: material.clone(),
+            transform: panel_transform,
+            ..default()
+        })
+        .id();
+
+    let second_panel = commands
+        .spawn(PbrBundle {
+            mesh: panel_mesh,
+            material,
+            transform: panel_transform,
+            ..default()
+        })
+        .id();
+
+    let bi_fold_joint = commands
+        .spawn(PbrBundle {
+            transform: Transform::from_xyz(panel_length, 0.0, 0.0),
+            ..default()
+        })
+        .insert(BiFoldJoint)
+        .add_child(second_panel)
+        .id();
+
+    commands
+        .entity(joint)
+        .push_children(&[first_panel, bi_fold_joint]);
+}
+
+/// A system to fold the second panel of folding doors against the first.
+pub(crate) fn update_bi_fold_joints(
+    joint_queries: Query<&Transform, With<DoorJoint>>,
+    mut bi_fold_joint_queries: Query<
+        (&Parent, &mut Transform),
+        (With<BiFoldJoint>, Without<DoorJoint>),
+    >,
+) {
+    for (parent, mut transform) in bi_fold_joint_queries.iter_mut() {
+        let Ok(joint_transform) = joint_queries.get(parent.get()) else {
+            continue;
+        };
+
+        let swing = joint_transform.rotation.to_euler(EulerRot::YXZ).0;
+        let rotation = Quat::from_rotation_y(-2.0 * swing);
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The default color of door frames.
+pub const DEFAULT_DOOR_FRAME_COLOR: Color = Color::srgb(0.35, 0.35, 0.38);
+
+/// A component to mark the frame pieces around a door.
+#[derive(Component)]
+pub struct DoorFrame;
+
+/// Spawn the top, bottom, left and right frame pieces around a door opening.
+///
+/// The pieces are children of the door entity, so they stay in place while the door moves.
+pub(crate) fn spawn_door_frame(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    dimensions: &DoorDimensions,
+    door_entity: Entity,
+) {
+    let frame_thickness = dimensions.frame_thickness;
+    let side_mesh = meshes.add(Cuboid::new(
+        frame_thickness,
+        dimensions.height + 2.0 * frame_thickness,
+        dimensions.frame_depth,
+    ));
+    let head_mesh = meshes.add(Cuboid::new(
+        dimensions.length,
+        frame_thickness,
+        dimensions.frame_depth,
+    ));
+
+    let pieces = [
+        // top
+        (
+            head_mesh.clone(),
+            Vec3::new(
+                dimensions.length / 2.0,
+                dimensions.height + frame_thickness / 2.0,
+                0.0,
+            ),
+        ),
+        // bottom
+        (
+            head_mesh,
+            Vec3::new(dimensions.length / 2.0, -frame_thickness / 2.0, 0.0),
+        ),
+        // left
+        (
+            side_mesh.clone(),
+            Vec3::new(-frame_thickness / 2.0, dimensions.height / 2.0, 0.0),
+        ),
+        // right
+        (
+            side_mesh,
+            Vec3::new(
+                dimensions.length + frame_thickness / 2.0,
+                dimensions.height / 2.0,
+                0.0,
+            ),
+        ),
+    ];
+
+    for (mesh, translation) in pieces {
+        let piece = commands
+            .spawn(PbrBundle {
+                mesh,
+                material: material.clone(),
+                transform: Transform::from_translation(translation),
+                ..default()
+            })
+            .insert(DoorFrame)
+            .id();
+        commands.entity(door_entity).add_child(piece);
+    }
+}
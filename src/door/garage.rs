@@ -0,0 +1,124 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// How much of the travel each garage door section waits for the section above it, as a
+/// fraction of the whole travel shared out between the sections.
+const GARAGE_SECTION_STAGGER: f32 = 0.5;
+
+/// A component to store the order of a garage door section, counted from the top.
+#[derive(Component, Clone, Copy, PartialEq)]
+pub struct GarageSectionIndex(pub u8);
+
+/// A component to store how far each section of a garage door has been raised.
+///
+/// It is kept on the door joint, with one progress from `0.0` to `1.0` per section.
+#[derive(Component, Clone)]
+pub struct GarageDoorState {
+    pub section_progress: Vec<f32>,
+    section_height: f32,
+    travel: f32,
+}
+
+/// Spawn the stacked sections of a garage door under its door joint.
+pub(crate) fn spawn_garage_sections(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    dimensions: &DoorDimensions,
+    sections: u8,
+    joint: Entity,
+) {
+    let sections = sections.max(1);
+    let section_height = dimensions.height / sections as f32;
+    let section_mesh = meshes.add(Cuboid::new(
+        dimensions.length,
+        section_height,
+        dimensions.thickness,
+    ));
+
+    for section in 0..sections {
+        let section_entity = commands
+            .spawn(PbrBundle {
+                mesh: section_mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_xyz(
+                    dimensions.length / 2.0,
+                    garage_section_rest_height(section, section_height, dimensions.height),
+                    0.0,
+                ),
+                ..default()
+            })
+            .insert(GarageSectionIndex(section))
+            .id();
+        commands.entity(joint).add_child(section_entity);
+    }
+
+    commands.entity(joint).insert(GarageDoorState {
+        section_progress: vec![0.0; sections as usize],
+        section_height,
+        travel: dimensions.height,
+    });
+}
+
+/// Get the height of the center of a closed garage door section.
+fn garage_section_rest_height(section: u8, section_height: f32, height: f32) -> f32 {
+    height - (section as f32 + 0.5) * section_height
+}
+
+/// A system to raise the sections of garage doors one after another.
+///
+/// The door joint rises with the door as a whole, and each section follows the joint once the
+/// sections above it have a head start.
+pub(crate) fn update_garage_sections(
+    mut joint_queries: Query<(&Transform, &Children, &mut GarageDoorState), With<DoorJoint>>,
+    mut section_queries: Query<(&GarageSectionIndex, &mut Transform), Without<DoorJoint>>,
+) {
+    for (transform, children, mut garage) in joint_queries.iter_mut() {
+        let position = transform.translation.y;
+        let open_fraction = door_open_fraction(position, garage.travel);
+        let sections = garage.section_progress.len();
+
+        for child in children.iter() {
+            let Ok((index, mut section_transform)) = section_queries.get_mut(*child) else {
+                continue;
+            };
+
+            let delay = if sections > 1 {
+                GARAGE_SECTION_STAGGER * index.0 as f32 / (sections - 1) as f32
+            } else {
+                0.0
+            };
+            let progress = ((open_fraction - delay) / (1.0 - delay)).clamp(0.0, 1.0);
+            if let Some(section_progress) = garage.section_progress.get_mut(index.0 as usize) {
+                if *section_progress != progress {
+                    *section_progress = progress;
+                }
+            }
+
+            // Sections are children of the joint, so they cancel out its rise
+            let height = garage_section_rest_height(index.0, garage.section_height, garage.travel)
+                + progress * garage.travel
+                - position;
+            if section_transform.translation.y != height {
+                section_transform.translation.y = height;
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The color of the door bounds drawn by `DoorGizmosPlugin`.
+pub const DOOR_BOUNDS_GIZMO_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+/// The color of the door swing arcs and travel lines drawn by `DoorGizmosPlugin`.
+pub const DOOR_TRAVEL_GIZMO_COLOR: Color = Color::srgb(1.0, 0.8, 0.0);
+
+/// A resource to turn the door debug gizmos on and off at runtime.
+#[derive(Resource)]
+pub struct DrawDoorGizmos {
+    pub enabled: bool,
+}
+
+impl Default for DrawDoorGizmos {
+    fn default() -> Self {
+        DrawDoorGizmos { enabled: true }
+    }
+}
+
+/// A plugin to draw debug gizmos for every door.
+///
+/// Each door gets its bounding box, swinging doors get an arc over their swing and sliding doors
+/// get a line along their travel. It needs Bevy's gizmos, which `DefaultPlugins` provides.
+pub struct DoorGizmosPlugin;
+
+impl Plugin for DoorGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DrawDoorGizmos>();
+        app.add_systems(Update, draw_door_gizmos.run_if(door_gizmos_enabled));
+    }
+}
+
+fn door_gizmos_enabled(draw_door_gizmos: Res<DrawDoorGizmos>) -> bool {
+    draw_door_gizmos.enabled
+}
+
+/// A system to draw the door bounds and travel.
+fn draw_door_gizmos(
+    mut gizmos: Gizmos,
+    door_queries: Query<(
+        &DoorProperties,
+        &DoorDimensions,
+        &GlobalTransform,
+        Has<DoorLeaf>,
+    )>,
+) {
+    for (properties, dimensions, global_transform, is_leaf) in door_queries.iter() {
+        let length = dimensions.length;
+        let height = dimensions.height;
+
+        // Leaves sit inside the bounds of their parent door
+        if !is_leaf {
+            gizmos.cuboid(
+                global_transform.mul_transform(
+                    Transform::from_xyz(length / 2.0, height / 2.0, 0.0).with_scale(Vec3::new(
+                        length,
+                        height,
+                        dimensions.thickness,
+                    )),
+                ),
+                DOOR_BOUNDS_GIZMO_COLOR,
+            );
+        }
+
+        let (_, rotation, translation) = global_transform.to_scale_rotation_translation();
+        let world_point = |point: Vec3| global_transform.transform_point(point);
+        let swing_value = properties.swing_value;
+
+        // A right hinged door swings about the far end of its length, facing back along it
+        let (hinge, hinge_rotation) = match properties.hinge_side() {
+            HingeSide::Right => (
+                Vec3::new(length, 0.0, 0.0),
+                rotation * Quat::from_rotation_y(std::f32::consts::PI),
+            ),
+            HingeSide::Left => (Vec3::ZERO, rotation),
+        };
+        let pivot = world_point(hinge + properties.hinge_anchor_offset());
+        let hinge_sign = properties.hinge_side().signum();
+
+        match properties.door_type {
+            DoorType::SingleSwinging => {
+                draw_swing_arcs(
+                    &mut gizmos,
+                    properties,
+                    length,
+                    properties.directed_swing_value() * hinge_sign,
+                    pivot,
+                    hinge_rotation,
+                );
+            }
+            DoorType::Folding | DoorType::BiFold { panels: 0..=2 } => {
+                gizmos.arc_3d(
+                    swing_value,
+                    length,
+                    translation,
+                    rotation,
+                    DOOR_TRAVEL_GIZMO_COLOR,
+                );
+            }
+            DoorType::DoubleAction => {
+                draw_swing_arcs(
+                    &mut gizmos,
+                    properties,
+                    length,
+                    2.0 * swing_value.abs(),
+                    pivot,
+                    hinge_rotation * Quat::from_rotation_y(-swing_value.abs()),
+                );
+            }
+            DoorType::SingleSliding | DoorType::PocketDoor { .. } => {
+                gizmos.line(
+                    world_point(Vec3::new(length / 2.0, height / 2.0, 0.0)),
+                    world_point(Vec3::new(length / 2.0 + swing_value, height / 2.0, 0.0)),
+                    DOOR_TRAVEL_GIZMO_COLOR,
+                );
+            }
+            DoorType::RollUp | DoorType::Garage { .. } => {
+                gizmos.line(
+                    world_point(Vec3::new(length / 2.0, 0.0, 0.0)),
+                    world_point(Vec3::new(length / 2.0, swing_value, 0.0)),
+                    DOOR_TRAVEL_GIZMO_COLOR,
+                );
+            }
+            DoorType::Revolving { .. } => {
+                gizmos.circle(
+                    world_point(Vec3::new(length / 2.0, 0.0, 0.0)),
+                    rotation * Dir3::Y,
+                    length / 2.0,
+                    DOOR_TRAVEL_GIZMO_COLOR,
+                );
+            }
+            // Double doors are drawn by their leaves
+            DoorType::DoubleSliding
+            | DoorType::DoubleSwinging
+            | DoorType::DoubleFolding
+            | DoorType::BiFold { .. } => {}
+        }
+    }
+}
+
+/// Draw the arcs swept by a swinging door panel about its pivot.
+///
+/// The part of the panel behind a pivot set in from the hinged edge gets an arc of its own, on the
+/// opposite side of the pivot.
+fn draw_swing_arcs(
+    gizmos: &mut Gizmos,
+    properties: &DoorProperties,
+    length: f32,
+    angle: f32,
+    pivot: Vec3,
+    rotation: Quat,
+) {
+    let inset = properties.pivot_inset();
+    let behind = rotation * Quat::from_rotation_y(std::f32::consts::PI);
+    for (radius, rotation) in [(length - inset, rotation), (inset, behind)] {
+        if radius > 0.0 {
+            gizmos.arc_3d(angle, radius, pivot, rotation, DOOR_TRAVEL_GIZMO_COLOR);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to give a door glass panels.
+///
+/// Add it to a door entity next to the `DoorBundle`, before the door is spawned.
+#[derive(Component, Clone)]
+pub struct DoorGlass {
+    pub tint: Color,
+    pub alpha: f32,
+    pub transmission: f32,
+    pub ior: f32,
+}
+
+impl Default for DoorGlass {
+    fn default() -> Self {
+        DoorGlass {
+            tint: Color::srgb(0.9, 0.95, 1.0),
+            alpha: 0.3,
+            transmission: 0.9,
+            ior: 1.5,
+        }
+    }
+}
+
+impl DoorGlass {
+    /// Create the material for a glass panel of the given thickness.
+    pub fn material(&self, thickness: f32) -> StandardMaterial {
+        StandardMaterial {
+            base_color: self.tint.with_alpha(self.alpha),
+            alpha_mode: AlphaMode::Blend,
+            specular_transmission: self.transmission,
+            diffuse_transmission: 0.0,
+            thickness,
+            ior: self.ior,
+            perceptual_roughness: 0.05,
+            reflectance: 0.5,
+            ..default()
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to put a door in a named group, so the whole group can be opened or closed with a
+/// single `DoorEvent`.
+///
+/// Add it to a door entity next to the `DoorBundle`. A door belongs to at most one group, and can
+/// still be addressed on its own by name or entity.
+#[derive(Component, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct DoorGroup(pub String);
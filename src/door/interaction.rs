@@ -0,0 +1,234 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use bevy::ecs::system::SystemParam;
+use bevy::render::primitives::Aabb;
+use bevy::window::PrimaryWindow;
+
+/// A door panel hit by a ray.
+#[derive(Clone, Copy, Debug)]
+pub struct DoorRayHit {
+    /// The entity the `DoorBundle` was spawned on.
+    pub door_entity: Entity,
+    /// The joint the hit panel moves with.
+    pub joint: Entity,
+    /// The panel mesh that was hit.
+    pub panel: Entity,
+    /// The distance along the ray to the hit.
+    pub distance: f32,
+}
+
+/// A system parameter to find the door under the cursor.
+///
+/// The ray is cast from the first active 3D camera through the cursor on the primary window, and
+/// tested against the bounds of the meshes under each door joint.
+#[derive(SystemParam)]
+pub struct DoorCursorRay<'w, 's> {
+    window_queries: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera_queries: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<Camera3d>>,
+    leaf_queries: Query<'w, 's, &'static Parent, With<DoorLeaf>>,
+    joint_queries: Query<'w, 's, (Entity, &'static Parent), With<DoorJoint>>,
+    children_queries: Query<'w, 's, &'static Children>,
+    panel_queries: Query<'w, 's, (&'static Aabb, &'static GlobalTransform)>,
+}
+
+impl DoorCursorRay<'_, '_> {
+    /// Get the ray through the cursor, if the cursor is over the primary window.
+    pub fn cursor_ray(&self) -> Option<Ray3d> {
+        let cursor_position = self.window_queries.get_single().ok()?.cursor_position()?;
+        self.camera_queries
+            .iter()
+            .filter(|(camera, _)| camera.is_active)
+            .find_map(|(camera, camera_transform)| {
+                camera.viewport_to_world(camera_transform, cursor_position)
+            })
+    }
+
+    /// Get the nearest door panel under the cursor.
+    pub fn hovered_door(&self) -> Option<DoorRayHit> {
+        let ray = self.cursor_ray()?;
+        let mut nearest: Option<DoorRayHit> = None;
+
+        for (joint, parent) in self.joint_queries.iter() {
+            for panel in self.children_queries.iter_descendants(joint) {
+                let Ok((aabb, global_transform)) = self.panel_queries.get(panel) else {
+                    continue;
+                };
+                let Some(distance) = ray_aabb_distance(ray, aabb, global_transform) else {
+                    continue;
+                };
+                if nearest.map_or(true, |hit| distance < hit.distance) {
+                    nearest = Some(DoorRayHit {
+                        door_entity: door_root(parent.get(), &self.leaf_queries),
+                        joint,
+                        panel,
+                        distance,
+                    });
+                }
+            }
+        }
+        nearest
+    }
+}
+
+/// Get the distance along a ray to where it enters a mesh's bounds, if it hits them at all.
+fn ray_aabb_distance(ray: Ray3d, aabb: &Aabb, global_transform: &GlobalTransform) -> Option<f32> {
+    // Test in the mesh's space, where the bounds are axis aligned
+    let world_to_mesh = global_transform.affine().inverse();
+    let origin = world_to_mesh.transform_point3(ray.origin);
+    let direction = world_to_mesh.transform_vector3(*ray.direction);
+    let min = Vec3::from(aabb.min());
+    let max = Vec3::from(aabb.max());
+
+    let mut near = 0.0_f32;
+    let mut far = f32::INFINITY;
+    for axis in 0..3 {
+        if direction[axis].abs() < f32::EPSILON {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (min[axis] - origin[axis]) / direction[axis];
+        let t2 = (max[axis] - origin[axis]) / direction[axis];
+        near = near.max(t1.min(t2));
+        far = far.min(t1.max(t2));
+        if near > far {
+            return None;
+        }
+    }
+    Some(near)
+}
+
+/// A component to highlight a door while the cursor is over it.
+///
+/// Add it to a door entity next to the `DoorBundle`. The panel under the cursor glows in the given
+/// color by swapping its material for a copy with that emissive color, and gets its own material
+/// back once the cursor leaves it. The thickness is the outline width for renderers that draw
+/// outlines, the emissive highlight ignores it.
+#[derive(Component, Clone, Copy)]
+pub struct DoorHighlight {
+    pub color: Color,
+    pub thickness: f32,
+}
+
+impl DoorHighlight {
+    /// Create a new door highlight component.
+    pub fn new(color: Color, thickness: f32) -> Self {
+        DoorHighlight { color, thickness }
+    }
+}
+
+/// A plugin to open and close doors by clicking on them.
+///
+/// A left click on a closed or closing door opens it, and a click on any other door closes it.
+/// Doors with a `DoorHighlight` are highlighted while the cursor is over them. It needs a 3D
+/// camera, a primary window and the door meshes' bounds, which `DefaultPlugins` provides, and it
+/// can be added next to `BevyDoorPlugin` or on its own.
+pub struct DoorInteractionPlugin;
+
+impl Plugin for DoorInteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DoorEvent>();
+        app.add_systems(
+            Update,
+            handle_door_raycast_interaction.in_set(DoorSystemSet::Request),
+        );
+        app.add_systems(Update, update_door_highlight);
+    }
+}
+
+/// A door panel that is highlighted, along with the material it had before.
+struct HighlightedPanel {
+    panel: Entity,
+    material: Handle<StandardMaterial>,
+}
+
+/// A system to toggle the door under the cursor when the left mouse button is pressed.
+fn handle_door_raycast_interaction(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    cursor_ray: DoorCursorRay,
+    state_queries: Query<&DoorState>,
+    mut door_requests: EventWriter<DoorEvent>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(hit) = cursor_ray.hovered_door() else {
+        return;
+    };
+
+    match state_queries.get(hit.joint) {
+        Ok(DoorState::Closed) | Ok(DoorState::Closing) => {
+            door_requests.send(DoorEvent::open_entity(hit.door_entity));
+        }
+        _ => {
+            door_requests.send(DoorEvent::close_entity(hit.door_entity));
+        }
+    }
+}
+
+/// A system to highlight the door panel under the cursor.
+///
+/// Only the panel mesh that was hit is highlighted, never the joint it moves with.
+fn update_door_highlight(
+    mut highlighted: Local<Option<HighlightedPanel>>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    cursor_ray: DoorCursorRay,
+    highlight_queries: Query<&DoorHighlight>,
+    material_queries: Query<&Handle<StandardMaterial>>,
+) {
+    let hovered = cursor_ray.hovered_door().and_then(|hit| {
+        let highlight = highlight_queries.get(hit.door_entity).ok()?;
+        Some((hit.panel, highlight))
+    });
+    let hovered_panel = hovered.map(|(panel, _)| panel);
+    if highlighted.as_ref().map(|highlighted| highlighted.panel) == hovered_panel {
+        return;
+    }
+
+    // Give the panel that was highlighted its own material back
+    if let Some(previous) = highlighted.take() {
+        if let Ok(highlight_material) = material_queries.get(previous.panel) {
+            materials.remove(highlight_material);
+        }
+        if let Some(mut panel) = commands.get_entity(previous.panel) {
+            panel.insert(previous.material);
+        }
+    }
+
+    let Some((panel, highlight)) = hovered else {
+        return;
+    };
+    let Ok(material) = material_queries.get(panel) else {
+        return;
+    };
+    let Some(mut highlight_material) = materials.get(material).cloned() else {
+        return;
+    };
+    highlight_material.emissive = highlight.color.into();
+    commands
+        .entity(panel)
+        .insert(materials.add(highlight_material));
+    *highlighted = Some(HighlightedPanel {
+        panel,
+        material: material.clone(),
+    });
+}
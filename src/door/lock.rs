@@ -0,0 +1,160 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to lock a door, so it ignores requests to open it.
+///
+/// It is part of the `DoorBundle` and starts unlocked. Lock and unlock doors with
+/// `DoorLockEvent`, or with `DoorEvent::lock` and `DoorEvent::unlock`.
+#[derive(Component, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct DoorLock {
+    pub locked: bool,
+    pub reason: String,
+}
+
+impl DoorLock {
+    /// Create a new locked door lock component.
+    pub fn new(reason: String) -> Self {
+        DoorLock {
+            locked: true,
+            reason,
+        }
+    }
+}
+
+/// A Bevy event to lock or unlock a door by name.
+#[derive(Event)]
+pub struct DoorLockEvent {
+    name: String,
+    locked: bool,
+}
+
+impl DoorLockEvent {
+    pub fn lock(name: String) -> Self {
+        DoorLockEvent { name, locked: true }
+    }
+
+    pub fn unlock(name: String) -> Self {
+        DoorLockEvent {
+            name,
+            locked: false,
+        }
+    }
+}
+
+/// Whether the door or the composite door it belongs to is locked.
+pub(crate) fn door_locked(
+    door_entity: Entity,
+    leaf_queries: &Query<&Parent, With<DoorLeaf>>,
+    lock_queries: &Query<&DoorLock>,
+) -> bool {
+    let locked = |entity| lock_queries.get(entity).is_ok_and(|lock| lock.locked);
+    locked(door_entity) || locked(door_root(door_entity, leaf_queries))
+}
+
+/// A system to lock and unlock doors based on the door and door lock events.
+///
+/// The events are resolved to doors like any other door event, so a name shared by several doors,
+/// a leaf suffix, a group or every door can be locked at once.
+///
+/// Locking a door that is opening or closing snaps it closed and sends a `DoorStateChanged`.
+/// Unlocking leaves the door where it is, and open requests are accepted again from the same
+/// frame.
+pub(crate) fn update_door_locks(
+    mut commands: Commands,
+    mut door_requests: EventReader<DoorEvent>,
+    mut lock_requests: EventReader<DoorLockEvent>,
+    mut state_changes: EventWriter<DoorStateChanged>,
+    door_targets: DoorEventTargets,
+    children_queries: Query<&Children>,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    door_property_queries: Query<&DoorProperties>,
+    mut lock_queries: Query<&mut DoorLock>,
+    mut joint_queries: Query<SnappedDoorJoint, With<DoorJoint>>,
+) {
+    let mut requests: Vec<(Entity, bool)> = Vec::new();
+    for door_request in door_requests.read() {
+        let locked = match door_request.action {
+            DoorAction::Lock => true,
+            DoorAction::Unlock => false,
+            _ => continue,
+        };
+        requests.extend(
+            door_targets
+                .resolve(door_request)
+                .into_iter()
+                .map(|door_entity| (door_entity, locked)),
+        );
+    }
+    for lock_request in lock_requests.read() {
+        requests.extend(
+            door_targets
+                .resolve_name(&lock_request.name)
+                .into_iter()
+                .map(|door_entity| (door_entity, lock_request.locked)),
+        );
+    }
+
+    for (door_entity, locked) in requests {
+        let Ok(door_properties) = door_property_queries.get(door_entity) else {
+            continue;
+        };
+
+        match lock_queries.get_mut(door_entity) {
+            Ok(mut lock) => lock.locked = locked,
+            Err(_) => {
+                commands.entity(door_entity).insert(DoorLock {
+                    locked,
+                    ..Default::default()
+                });
+            }
+        }
+
+        if !locked {
+            log::info!("Unlocking door {}", door_properties.name);
+            continue;
+        }
+        log::info!("Locking door {}", door_properties.name);
+
+        let mut old_state = None;
+        for joint in children_queries.iter_descendants(door_entity) {
+            let Ok(mut snapped) = joint_queries.get_mut(joint) else {
+                continue;
+            };
+            if *snapped.state != DoorState::Opening && *snapped.state != DoorState::Closing {
+                continue;
+            }
+
+            let properties = door_property_queries
+                .get(snapped.parent.get())
+                .expect("Door properties not found");
+            old_state = old_state.or(snapped.snap(properties, DoorGoal::Closed));
+        }
+
+        if let Some(old_state) = old_state {
+            state_changes.send(DoorStateChanged {
+                door_entity: door_root(door_entity, &leaf_queries),
+                name: door_properties.name.clone(),
+                old_state,
+                new_state: DoorState::Closed,
+            });
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to override the colors of a door.
+///
+/// Add it to a door entity next to the `DoorBundle`, before the door is spawned. Doors without it
+/// use the `DoorTheme` for their panels and `DEFAULT_DOOR_FRAME_COLOR` for their frame. A handle
+/// is only added to the panels when `handle_color` is set. Both leaves of a double door use the
+/// colors of their parent door.
+#[derive(Component, Clone)]
+pub struct DoorMaterial {
+    pub panel_color: Color,
+    pub frame_color: Option<Color>,
+    pub handle_color: Option<Color>,
+}
+
+impl Default for DoorMaterial {
+    fn default() -> Self {
+        DoorMaterial {
+            panel_color: DEFAULT_DOOR_COLOR,
+            frame_color: None,
+            handle_color: None,
+        }
+    }
+}
+
+impl From<Color> for DoorMaterial {
+    fn from(panel_color: Color) -> Self {
+        DoorMaterial::new(panel_color)
+    }
+}
+
+impl DoorMaterial {
+    /// Create a new door material component with the given panel color.
+    pub fn new(panel_color: Color) -> Self {
+        DoorMaterial {
+            panel_color,
+            frame_color: None,
+            handle_color: None,
+        }
+    }
+
+    /// Set the color of the door frame.
+    pub fn with_frame_color(mut self, frame_color: Color) -> Self {
+        self.frame_color = Some(frame_color);
+        self
+    }
+
+    /// Set the color of the door handle.
+    pub fn with_handle_color(mut self, handle_color: Color) -> Self {
+        self.handle_color = Some(handle_color);
+        self
+    }
+}
+
+/// The size of a door handle, along the door length, height and thickness.
+const DOOR_HANDLE_SIZE: Vec3 = Vec3::new(0.12, 0.03, 0.06);
+
+/// Spawn a handle through a door panel, near the edge away from the hinge.
+pub(crate) fn spawn_door_handle(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    dimensions: &DoorDimensions,
+    hinged_right: bool,
+    panel: Entity,
+) {
+    // The panel is centred on the door, so shift the handle to its free edge
+    let free_edge = dimensions.length / 2.0 - DOOR_HANDLE_SIZE.x;
+    let handle_offset = if hinged_right { -free_edge } else { free_edge };
+
+    let handle = commands
+        .spawn(PbrBundle {
+            mesh: meshes.add(Cuboid::new(
+                DOOR_HANDLE_SIZE.x,
+                DOOR_HANDLE_SIZE.y,
+                dimensions.thickness + DOOR_HANDLE_SIZE.z,
+            )),
+            material,
+            transform: Transform::from_xyz(handle_offset, 0.0, 0.0),
+            ..default()
+        })
+        .id();
+    commands.entity(panel).add_child(handle);
+}
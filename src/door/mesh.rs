@@ -0,0 +1,55 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to replace the generated door meshes, for example with meshes from a GLTF asset.
+///
+/// Add it to a door entity next to the `DoorBundle`, before the door is spawned. The panel mesh is
+/// placed where the generated panel would be, centred on the door opening, and double doors use it
+/// for each of their leaves. Doors made of several panels, such as folding, garage and revolving
+/// doors, keep their generated panels and only use the material.
+#[derive(Component, Clone)]
+pub struct DoorMeshOverride {
+    pub panel_mesh: Handle<Mesh>,
+    pub joint_mesh: Option<Handle<Mesh>>,
+    pub material: Option<Handle<StandardMaterial>>,
+}
+
+impl DoorMeshOverride {
+    /// Create a new door mesh override component with the given panel mesh.
+    pub fn new(panel_mesh: Handle<Mesh>) -> Self {
+        DoorMeshOverride {
+            panel_mesh,
+            joint_mesh: None,
+            material: None,
+        }
+    }
+
+    /// Set the mesh shown at the door joint.
+    pub fn with_joint_mesh(mut self, joint_mesh: Handle<Mesh>) -> Self {
+        self.joint_mesh = Some(joint_mesh);
+        self
+    }
+
+    /// Set the material used for the door panels.
+    pub fn with_material(mut self, material: Handle<StandardMaterial>) -> Self {
+        self.material = Some(material);
+        self
+    }
+}
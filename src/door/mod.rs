@@ -17,35 +17,346 @@
 */
 // =========================================================================
 use super::*;
+use bevy::ecs::event::EventId;
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::SystemParam;
+mod access;
+mod airlock;
+mod analytics;
+mod animation;
+mod auto_close;
+mod auto_open;
+mod blocked;
+mod builder;
+mod callback;
+mod clearance;
+mod clone;
+mod config;
+mod constraint;
+mod despawn;
+mod event_log;
+mod floor_plan;
+mod fold;
+mod frame;
+mod garage;
+mod gizmos;
+mod glass;
+mod group;
+mod interaction;
+mod lock;
+mod material;
+mod mesh;
+#[cfg(feature = "navmesh")]
+mod navmesh;
+mod obstruction;
+mod pause;
+mod pocket;
+mod proximity;
+#[cfg(feature = "rapier")]
+mod rapier;
+mod registry;
+mod resize;
+mod revolving;
+mod safety;
+#[cfg(feature = "serde")]
+mod scene;
+mod schedule;
+mod sensor;
+mod signal;
+mod sound;
+mod status;
+mod stuck;
+#[cfg(test)]
+mod tests;
+mod theme;
+#[cfg(feature = "urdf")]
+mod urdf;
+
+pub use access::*;
+pub use airlock::*;
+pub use analytics::*;
+pub use animation::*;
+pub use auto_close::*;
+pub use auto_open::*;
+pub use blocked::*;
+pub use builder::*;
+pub use callback::*;
+pub use clearance::*;
+pub use clone::*;
+pub use config::*;
+pub use constraint::*;
+pub use despawn::*;
+pub use event_log::*;
+pub use floor_plan::*;
+pub use fold::*;
+pub use frame::*;
+pub use garage::*;
+pub use gizmos::*;
+pub use glass::*;
+pub use group::*;
+pub use interaction::*;
+pub use lock::*;
+pub use material::*;
+pub use mesh::*;
+#[cfg(feature = "navmesh")]
+pub use navmesh::*;
+pub use obstruction::*;
+pub use pause::*;
+pub use pocket::*;
+pub use proximity::*;
+#[cfg(feature = "rapier")]
+pub use rapier::*;
+pub use registry::*;
+pub use revolving::*;
+pub use safety::*;
+#[cfg(feature = "serde")]
+pub use scene::*;
+pub use schedule::*;
+pub use sensor::*;
+pub use signal::*;
+pub use sound::*;
+pub use status::*;
+pub use stuck::*;
+pub use theme::*;
+#[cfg(feature = "urdf")]
+pub use urdf::*;
 
 /// A Bevy event for door actions.
+///
+/// Doors are addressed by name, by the door entity the `DoorBundle` was spawned on, by the name
+/// of their `DoorGroup`, or all at once. An event addressed by entity ignores its name, and an
+/// event addressed by name ignores its group, so a door is never moved twice by one event.
 #[derive(Event)]
 pub struct DoorEvent {
-    name: String,
-    goal: DoorGoal,
+    name: Option<String>,
+    entity: Option<Entity>,
+    group: Option<String>,
+    all: bool,
+    action: DoorAction,
+    side: DoorSide,
+    requester: Option<Entity>,
+}
+
+/// A enum to describe what a door event asks of the door.
+#[derive(Clone, Copy, PartialEq)]
+enum DoorAction {
+    Open,
+    OpenTo(f32),
+    Close,
+    Toggle,
+    Lock,
+    Unlock,
 }
 
 impl DoorEvent {
     pub fn open(name: String) -> Self {
-        return DoorEvent {
-            name,
-            goal: DoorGoal::Open,
-        };
+        DoorEvent::named(name, DoorAction::Open)
+    }
+
+    /// Open a door from the given side.
+    ///
+    /// Only `DoubleAction` doors care about the side, they swing away from it.
+    pub fn open_from(name: String, side: DoorSide) -> Self {
+        DoorEvent {
+            side,
+            ..DoorEvent::named(name, DoorAction::Open)
+        }
+    }
+
+    /// Open a door part of the way, by the given fraction of its swing value.
+    ///
+    /// The fraction is clamped between `0.0` and `1.0`, and the door comes to rest in
+    /// `DoorState::PartiallyOpen` at that fraction. Revolving doors open fully.
+    pub fn open_to(name: String, fraction: f32) -> Self {
+        DoorEvent::named(name, DoorAction::OpenTo(fraction))
     }
 
     pub fn close(name: String) -> Self {
-        return DoorEvent {
-            name,
-            goal: DoorGoal::Closed,
-        };
+        DoorEvent::named(name, DoorAction::Close)
+    }
+
+    /// Flip a door relative to its current state.
+    ///
+    /// The toggle is resolved against the door's state when the event is read: a `Closed` or
+    /// `Closing` door opens, and an `Open` or `Opening` door closes, so a door caught mid-motion
+    /// reverses. Several toggles sent in the same frame are applied in the order they were sent.
+    pub fn toggle(name: String) -> Self {
+        DoorEvent::named(name, DoorAction::Toggle)
+    }
+
+    /// Lock a door, so it ignores requests to open it until it is unlocked.
+    ///
+    /// A door that is opening or closing when it is locked snaps closed.
+    pub fn lock(name: String) -> Self {
+        DoorEvent::named(name, DoorAction::Lock)
+    }
+
+    /// Unlock a door, so it can be opened again.
+    pub fn unlock(name: String) -> Self {
+        DoorEvent::named(name, DoorAction::Unlock)
+    }
+
+    /// Open the door spawned on the given entity, without looking it up by name.
+    pub fn open_entity(entity: Entity) -> Self {
+        DoorEvent::addressed(entity, DoorAction::Open)
+    }
+
+    /// Close the door spawned on the given entity, without looking it up by name.
+    pub fn close_entity(entity: Entity) -> Self {
+        DoorEvent::addressed(entity, DoorAction::Close)
+    }
+
+    /// Open every door in the given `DoorGroup`.
+    pub fn open_group(group: String) -> Self {
+        DoorEvent::grouped(group, DoorAction::Open)
+    }
+
+    /// Close every door in the given `DoorGroup`.
+    pub fn close_group(group: String) -> Self {
+        DoorEvent::grouped(group, DoorAction::Close)
+    }
+
+    /// Open every door, for example when a fire alarm goes off.
+    pub fn open_all() -> Self {
+        DoorEvent::everywhere(DoorAction::Open)
+    }
+
+    /// Close every door.
+    pub fn close_all() -> Self {
+        DoorEvent::everywhere(DoorAction::Close)
+    }
+
+    /// Lock every door in the given `DoorGroup`.
+    pub fn lock_group(group: String) -> Self {
+        DoorEvent::grouped(group, DoorAction::Lock)
+    }
+
+    /// Unlock every door in the given `DoorGroup`.
+    pub fn unlock_group(group: String) -> Self {
+        DoorEvent::grouped(group, DoorAction::Unlock)
+    }
+
+    /// Lock every door, for example during a lockdown.
+    pub fn lock_all() -> Self {
+        DoorEvent::everywhere(DoorAction::Lock)
+    }
+
+    /// Unlock every door.
+    pub fn unlock_all() -> Self {
+        DoorEvent::everywhere(DoorAction::Unlock)
+    }
+
+    /// The name of the door this event is addressed to, if it is addressed by name.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The door entity this event is addressed to, if it is addressed by entity.
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+
+    /// The door group this event is addressed to, if it is addressed by group.
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Whether this event is addressed to every door.
+    pub fn is_all(&self) -> bool {
+        self.all
+    }
+
+    /// Set the entity asking for the door to move, so its `AccessLevel` is checked.
+    pub fn with_requester(mut self, requester: Entity) -> Self {
+        self.requester = Some(requester);
+        self
+    }
+
+    /// The entity asking for the door to move, if there is one.
+    pub fn requester(&self) -> Option<Entity> {
+        self.requester
+    }
+
+    /// Whether this event would open a door whose joints are in the given states.
+    pub(crate) fn opens<'a>(&self, mut states: impl Iterator<Item = &'a DoorState>) -> bool {
+        match self.action {
+            DoorAction::Open => true,
+            DoorAction::OpenTo(fraction) => DoorGoal::at_fraction(fraction) != DoorGoal::Closed,
+            // A toggle opens a door that is not already open or opening
+            DoorAction::Toggle => {
+                states.all(|state| *state != DoorState::Open && *state != DoorState::Opening)
+            }
+            DoorAction::Close | DoorAction::Lock | DoorAction::Unlock => false,
+        }
+    }
+
+    fn named(name: String, action: DoorAction) -> Self {
+        DoorEvent {
+            name: Some(name),
+            entity: None,
+            group: None,
+            all: false,
+            action,
+            side: DoorSide::default(),
+            requester: None,
+        }
+    }
+
+    fn addressed(entity: Entity, action: DoorAction) -> Self {
+        DoorEvent {
+            name: None,
+            entity: Some(entity),
+            group: None,
+            all: false,
+            action,
+            side: DoorSide::default(),
+            requester: None,
+        }
+    }
+
+    fn grouped(group: String, action: DoorAction) -> Self {
+        DoorEvent {
+            name: None,
+            entity: None,
+            group: Some(group),
+            all: false,
+            action,
+            side: DoorSide::default(),
+            requester: None,
+        }
+    }
+
+    fn everywhere(action: DoorAction) -> Self {
+        DoorEvent {
+            name: None,
+            entity: None,
+            group: None,
+            all: true,
+            action,
+            side: DoorSide::default(),
+            requester: None,
+        }
     }
 }
 
+/// A Bevy event sent when a door moves into a new state.
+///
+/// The leaves of a composite door send a single event for the door as a whole, addressed to
+/// the door entity the `DoorBundle` was spawned on.
+#[derive(Event)]
+pub struct DoorStateChanged {
+    pub door_entity: Entity,
+    pub name: String,
+    pub old_state: DoorState,
+    pub new_state: DoorState,
+}
+
 /// A component bundle for doors.
 #[derive(Bundle, Default)]
 pub struct DoorBundle {
     pub door_properties: DoorProperties,
     pub door_dimensions: DoorDimensions,
+    pub door_lock: DoorLock,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
     pub visibility: Visibility,
@@ -53,12 +364,52 @@ pub struct DoorBundle {
     pub view_visibility: ViewVisibility,
 }
 
+/// The default door speed, in units or radians per second.
+pub const DEFAULT_DOOR_SPEED: f32 = 0.6;
+
+/// The slowest a door is allowed to move, so it always reaches its goal.
+pub const MIN_DOOR_SPEED: f32 = 0.06;
+
+/// The smallest length, height and thickness a door panel is spawned with, in units.
+pub const MIN_DOOR_DIMENSION: f32 = 0.01;
+
+/// The default distance at which a moving door snaps onto its goal, in units or radians.
+pub const DEFAULT_DOOR_SNAP_TOLERANCE: f32 = 0.02;
+
 /// A component to store door properties.
-#[derive(Component, Default)]
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct DoorProperties {
     name: String,
     swing_value: f32,
     door_type: DoorType,
+    speed: Option<f32>,
+    hinge_side: HingeSide,
+    swing_direction: Option<SwingDirection>,
+    snap_tolerance: f32,
+    anchor_offset: Vec3,
+    easing: DoorAnimationCurve,
+}
+
+impl Default for DoorProperties {
+    fn default() -> Self {
+        DoorProperties {
+            name: String::default(),
+            swing_value: 0.0,
+            door_type: DoorType::default(),
+            speed: None,
+            hinge_side: HingeSide::default(),
+            swing_direction: None,
+            snap_tolerance: DEFAULT_DOOR_SNAP_TOLERANCE,
+            anchor_offset: Vec3::ZERO,
+            easing: DoorAnimationCurve::Linear,
+        }
+    }
 }
 
 impl DoorProperties {
@@ -68,86 +419,581 @@ impl DoorProperties {
             name,
             swing_value,
             door_type,
+            speed: None,
+            hinge_side: HingeSide::default(),
+            swing_direction: None,
+            snap_tolerance: DEFAULT_DOOR_SNAP_TOLERANCE,
+            anchor_offset: Vec3::ZERO,
+            easing: DoorAnimationCurve::Linear,
+        }
+    }
+
+    /// Set the distance at which the moving door snaps onto its goal, in units for sliding and
+    /// rising doors and radians for swinging doors.
+    ///
+    /// A larger tolerance finishes the movement sooner with a visible snap, a smaller one eases
+    /// closer to the goal first. Doors always snap within two frames of travel at their speed, so
+    /// the tolerance only takes effect when it is larger than that.
+    pub fn with_snap_tolerance(mut self, snap_tolerance: f32) -> Self {
+        self.snap_tolerance = snap_tolerance.abs();
+        self
+    }
+
+    /// Set the jamb a swinging or double action door is hinged on.
+    ///
+    /// Other door types ignore it.
+    pub fn with_hinge_side(mut self, hinge_side: HingeSide) -> Self {
+        self.hinge_side = hinge_side;
+        self
+    }
+
+    /// Set the offset of the pivot from the hinged jamb of a swinging or double action door.
+    ///
+    /// The offset is in door space, with positive x pointing along the door away from the hinged
+    /// jamb whichever side it is on. The closed panel stays where it is and turns about the offset
+    /// pivot instead, so an offset of half the door length makes a centre hung pivot door. Other
+    /// door types ignore it.
+    pub fn with_anchor_offset(mut self, anchor_offset: Vec3) -> Self {
+        self.anchor_offset = anchor_offset;
+        self
+    }
+
+    /// Set the side of the wall a swinging door opens to, whatever the sign of its swing value.
+    ///
+    /// Without it, the sign of the swing value picks the side. Other door types ignore it.
+    pub fn with_swing_direction(mut self, swing_direction: SwingDirection) -> Self {
+        self.swing_direction = Some(swing_direction);
+        self
+    }
+
+    /// Set how the door eases in and out of its movement.
+    ///
+    /// A `DoorAnimationCurve` component on the door takes precedence over it.
+    pub fn with_easing(mut self, easing: DoorAnimationCurve) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set the door speed, in units per second for sliding doors and radians per second for
+    /// swinging doors.
+    ///
+    /// Speeds below `MIN_DOOR_SPEED` are clamped so the door never stalls.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        if speed.abs() < MIN_DOOR_SPEED {
+            log::warn!(
+                "Door {} speed {} is too slow, clamping to {}",
+                self.name,
+                speed,
+                MIN_DOOR_SPEED
+            );
+        }
+        self.speed = Some(speed.abs().max(MIN_DOOR_SPEED));
+        self
+    }
+
+    /// Get the door name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the door swing value.
+    pub fn swing_value(&self) -> f32 {
+        self.swing_value
+    }
+
+    /// Get the door type.
+    pub fn door_type(&self) -> &DoorType {
+        &self.door_type
+    }
+
+    /// Get how the door eases in and out of its movement.
+    pub fn easing(&self) -> DoorAnimationCurve {
+        self.easing
+    }
+
+    /// Get the distance at which the moving door snaps onto its goal.
+    pub fn snap_tolerance(&self) -> f32 {
+        self.snap_tolerance
+    }
+
+    /// Get the jamb the door is hinged on.
+    pub fn hinge_side(&self) -> HingeSide {
+        self.hinge_side
+    }
+
+    /// Get the offset of the pivot from the hinged jamb.
+    pub fn anchor_offset(&self) -> Vec3 {
+        self.anchor_offset
+    }
+
+    /// Get the side of the wall the door opens to, from the swing direction or the swing value.
+    pub fn swing_direction(&self) -> SwingDirection {
+        match self.swing_direction {
+            Some(swing_direction) => swing_direction,
+            None if self.swing_value < 0.0 => SwingDirection::Outward,
+            None => SwingDirection::Inward,
+        }
+    }
+
+    /// The swing value, signed by the swing direction if one is set on a swinging door.
+    fn directed_swing_value(&self) -> f32 {
+        match (&self.door_type, self.swing_direction) {
+            (DoorType::SingleSwinging | DoorType::DoubleSwinging, Some(swing_direction)) => {
+                self.swing_value.abs() * swing_direction.signum()
+            }
+            _ => self.swing_value,
+        }
+    }
+
+    /// Whether the door is hinged on its right jamb.
+    fn hinged_right(&self) -> bool {
+        match self.door_type {
+            DoorType::SingleSwinging | DoorType::DoubleAction => {
+                self.hinge_side == HingeSide::Right
+            }
+            _ => false,
+        }
+    }
+
+    /// The offset of the joint from the hinge in door space, mirrored for a right hinged door.
+    fn hinge_anchor_offset(&self) -> Vec3 {
+        match self.door_type {
+            DoorType::SingleSwinging | DoorType::DoubleAction => {
+                self.anchor_offset * Vec3::new(self.hinge_side.signum(), 1.0, 1.0)
+            }
+            _ => Vec3::ZERO,
+        }
+    }
+
+    /// How far the pivot of a swinging door is set in along its panel from the hinged edge.
+    ///
+    /// The part of the panel behind the pivot swings the opposite way to the rest of it.
+    fn pivot_inset(&self) -> f32 {
+        self.hinge_anchor_offset().x * self.hinge_side.signum()
+    }
+
+    /// Get the door speed.
+    ///
+    /// A door without a speed set with `with_speed` moves at the `default_speed` of the
+    /// `DoorPluginConfig` once spawned, and reports `DEFAULT_DOOR_SPEED` before.
+    pub fn speed(&self) -> f32 {
+        self.speed.unwrap_or(DEFAULT_DOOR_SPEED)
+    }
+
+    /// Replace non-finite values with their defaults.
+    fn sanitize(&mut self) {
+        if !self.swing_value.is_finite() {
+            log::warn!(
+                "Door {} swing value {} is not finite, using {}",
+                self.name,
+                self.swing_value,
+                DoorProperties::default().swing_value
+            );
+            self.swing_value = DoorProperties::default().swing_value;
+        }
+        if let Some(speed) = self.speed.filter(|speed| !speed.is_finite()) {
+            log::warn!(
+                "Door {} speed {} is not finite, using the default speed",
+                self.name,
+                speed
+            );
+            self.speed = None;
+        }
+        if !self.snap_tolerance.is_finite() {
+            log::warn!(
+                "Door {} snap tolerance {} is not finite, using {}",
+                self.name,
+                self.snap_tolerance,
+                DEFAULT_DOOR_SNAP_TOLERANCE
+            );
+            self.snap_tolerance = DEFAULT_DOOR_SNAP_TOLERANCE;
+        }
+        if !self.anchor_offset.is_finite() {
+            log::warn!(
+                "Door {} anchor offset {} is not finite, using {}",
+                self.name,
+                self.anchor_offset,
+                Vec3::ZERO
+            );
+            self.anchor_offset = Vec3::ZERO;
         }
     }
 }
 
 /// A component to store door dimensions.
-#[derive(Component, Default)]
+///
+/// Changing them after the door is spawned rebuilds the door at its new size.
+#[derive(Component, Clone, Default, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct DoorDimensions {
     length: f32,
     height: f32,
     thickness: f32,
+    frame_thickness: f32,
+    frame_depth: f32,
 }
 
 impl DoorDimensions {
     /// Create a new door dimensions component.
+    ///
+    /// A length, height or thickness below `MIN_DOOR_DIMENSION` is clamped with a warning when
+    /// the door is spawned.
     pub fn new(length: f32, height: f32, thickness: f32) -> Self {
         DoorDimensions {
             length,
             height,
             thickness,
+            frame_thickness: 0.0,
+            frame_depth: 0.0,
+        }
+    }
+
+    /// Give the door a frame around its opening.
+    ///
+    /// The frame thickness is the width of each frame piece and the depth is how far it reaches
+    /// into the wall. A frame thickness of `0.0` spawns no frame.
+    pub fn with_frame(mut self, frame_thickness: f32, frame_depth: f32) -> Self {
+        self.frame_thickness = frame_thickness.max(0.0);
+        self.frame_depth = frame_depth.max(0.0);
+        self
+    }
+
+    /// Replace non-finite values with their defaults, and clamp the panel size to
+    /// `MIN_DOOR_DIMENSION`.
+    ///
+    /// The leaves of double doors are sanitized again when they are spawned, so halving a
+    /// clamped length cannot shrink a leaf below the minimum either.
+    fn sanitize(&mut self, name: &str) {
+        let default = DoorDimensions::default();
+        for (label, value, default_value) in [
+            ("length", &mut self.length, default.length),
+            ("height", &mut self.height, default.height),
+            ("thickness", &mut self.thickness, default.thickness),
+            (
+                "frame thickness",
+                &mut self.frame_thickness,
+                default.frame_thickness,
+            ),
+            ("frame depth", &mut self.frame_depth, default.frame_depth),
+        ] {
+            if !value.is_finite() {
+                log::warn!(
+                    "Door {} {} {} is not finite, using {}",
+                    name,
+                    label,
+                    value,
+                    default_value
+                );
+                *value = default_value;
+            }
+        }
+
+        // A panel without a size spawns a degenerate mesh that cannot be seen
+        for (label, value) in [
+            ("length", &mut self.length),
+            ("height", &mut self.height),
+            ("thickness", &mut self.thickness),
+        ] {
+            if *value < MIN_DOOR_DIMENSION {
+                log::warn!(
+                    "Door {} {} {} is too small, clamping to {}",
+                    name,
+                    label,
+                    value,
+                    MIN_DOOR_DIMENSION
+                );
+                *value = MIN_DOOR_DIMENSION;
+            }
         }
     }
 }
 
 /// A enum to describe the door type.
+///
+/// With the `serde` feature, door types are tagged in snake case, such as `"single_sliding"`.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Default)]
+#[reflect(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum DoorType {
+    #[default]
     SingleSliding,
     DoubleSliding,
     SingleSwinging,
     DoubleSwinging,
+    /// A saloon style door that swings both ways and returns to center when closed.
+    DoubleAction,
+    /// A bi-fold door of two hinged panels that fold towards the jamb, the swing value is the
+    /// fold angle of the first panel.
+    Folding,
+    /// A pair of folding doors that fold towards opposite jambs.
+    DoubleFolding,
+    /// A bi-fold door with the given number of panels, two panels fold like `Folding` and four
+    /// panels fold like `DoubleFolding`.
+    BiFold {
+        panels: u8,
+    },
+    /// A set of wings on a central hub, one per segment, that turns at the swing value in radians
+    /// per second while open and parks when closed.
+    Revolving {
+        segments: u8,
+    },
+    /// A garage door that rises along y by the swing value, up to the door height.
+    RollUp,
+    /// A sliding door that disappears into a pocket in the wall, the sign of the swing value is
+    /// the side of the pocket.
+    PocketDoor {
+        wall_thickness: f32,
+    },
+    /// A sectional garage door that rises by its height, with the given number of stacked
+    /// sections that follow each other up from the top section.
+    Garage {
+        sections: u8,
+    },
 }
 
-impl Default for DoorType {
-    fn default() -> Self {
-        DoorType::SingleSliding
+impl DoorType {
+    /// Whether the door is spawned as two leaves under the door entity.
+    pub fn is_composite(&self) -> bool {
+        match self {
+            DoorType::DoubleSliding | DoorType::DoubleSwinging | DoorType::DoubleFolding => true,
+            DoorType::BiFold { panels } => *panels > 2,
+            _ => false,
+        }
+    }
+}
+
+/// A enum to describe the jamb a swinging door is hinged on, looking at the door from the front.
+///
+/// A `Left` hinge sits at the door origin and a `Right` hinge at the far end of its length. The
+/// swing of a right hinged door is mirrored, so a door with the same swing value opens to the same
+/// side of the wall whichever jamb it is hinged on.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Default)]
+#[reflect(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum HingeSide {
+    #[default]
+    Left,
+    Right,
+}
+
+impl HingeSide {
+    /// The sign applied to the swing of a door hinged on this side.
+    pub fn signum(&self) -> f32 {
+        match self {
+            HingeSide::Left => 1.0,
+            HingeSide::Right => -1.0,
+        }
+    }
+}
+
+/// A enum to describe the side of the wall a swinging door opens to.
+///
+/// `Inward` swings the free edge of the door towards negative Z, away from a viewer looking at
+/// its front, and `Outward` towards positive Z.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Default)]
+#[reflect(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum SwingDirection {
+    #[default]
+    Inward,
+    Outward,
+}
+
+impl SwingDirection {
+    /// The sign of the swing value for this direction on a left hinged door.
+    pub fn signum(&self) -> f32 {
+        match self {
+            SwingDirection::Inward => 1.0,
+            SwingDirection::Outward => -1.0,
+        }
+    }
+
+    /// The opposite direction.
+    pub fn opposite(&self) -> SwingDirection {
+        match self {
+            SwingDirection::Inward => SwingDirection::Outward,
+            SwingDirection::Outward => SwingDirection::Inward,
+        }
     }
 }
 
 /// A component to store the door's current state.
-#[derive(Component, PartialEq)]
+#[derive(Component, Clone, Copy, PartialEq, Debug, Reflect, Default)]
+#[reflect(Component, Default)]
 pub enum DoorState {
     Open,
+    #[default]
     Closed,
     Opening,
     Closing,
+    /// The door is at rest between its closed and open positions, by the given open fraction.
+    PartiallyOpen(f32),
+    /// The door stopped closing because its `DoorObstruction` sensor reports something in the way.
+    Obstructed,
+    /// The door stopped moving because its panel would run into an `Obstacle`.
+    Blocked,
 }
 
-impl Default for DoorState {
-    fn default() -> Self {
-        DoorState::Closed
+impl DoorState {
+    /// Get the resting state of a door at the given open fraction.
+    ///
+    /// A fraction of `0.0` or below is `Closed` and `1.0` or above is `Open`, so `PartiallyOpen`
+    /// always holds a fraction strictly between the two.
+    pub fn at_rest(open_fraction: f32) -> Self {
+        if open_fraction <= 0.0 {
+            DoorState::Closed
+        } else if open_fraction >= 1.0 {
+            DoorState::Open
+        } else {
+            DoorState::PartiallyOpen(open_fraction)
+        }
+    }
+}
+
+/// A component to store how far a door is open, from `0.0` when closed to `1.0` when open.
+///
+/// It is kept on each door joint next to the `DoorState` and updated every frame.
+#[derive(Component, Clone, Copy, PartialEq, Default)]
+pub struct DoorOpenProgress(f32);
+
+impl DoorOpenProgress {
+    /// The normalized open fraction of the door.
+    pub fn open_fraction(&self) -> f32 {
+        self.0
+    }
+}
+
+/// A component to store how fast a door joint moved over the last frame.
+///
+/// It is kept on each door joint and updated every frame from the change of its transform, in the
+/// space of its door. The linear velocity is in units per second and the angular velocity is an
+/// axis scaled by radians per second. Doors at rest and paused doors report zero.
+#[derive(Component, Clone, Copy, PartialEq, Default, Debug)]
+pub struct DoorVelocity {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
+
+impl DoorVelocity {
+    /// Get the velocity of a joint that moved from one transform to another over some seconds.
+    fn between(from: &Transform, to: &Transform, seconds: f32) -> Self {
+        if seconds <= 0.0 {
+            return DoorVelocity::default();
+        }
+        DoorVelocity {
+            linear: (to.translation - from.translation) / seconds,
+            angular: (to.rotation * from.rotation.inverse()).to_scaled_axis() / seconds,
+        }
+    }
+
+    /// Whether the joint is moving.
+    pub fn is_moving(&self) -> bool {
+        self.linear != Vec3::ZERO || self.angular != Vec3::ZERO
     }
 }
 
+/// A partially open door has reached its goal when its open fraction is this close to it.
+const DOOR_FRACTION_TOLERANCE: f32 = 1e-3;
+
 impl PartialEq<DoorGoal> for DoorState {
     fn eq(&self, other: &DoorGoal) -> bool {
-        match self {
-            DoorState::Open => other == &DoorGoal::Open,
-            DoorState::Closed => other == &DoorGoal::Closed,
+        match (self, other) {
+            (DoorState::Open, DoorGoal::Open) => true,
+            (DoorState::Closed, DoorGoal::Closed) => true,
+            (DoorState::PartiallyOpen(state), DoorGoal::PartiallyOpen(goal)) => {
+                (state - goal).abs() <= DOOR_FRACTION_TOLERANCE
+            }
+            // A blocked door is stuck on its way, so it never counts as having reached its goal
+            (DoorState::Blocked, _) => false,
             _ => false,
         }
     }
 }
 
 /// A component to store the door's goal state.
-#[derive(Component, PartialEq)]
+#[derive(Component, Clone, Copy, PartialEq, Debug, Reflect, Default)]
+#[reflect(Component, Default)]
 pub enum DoorGoal {
     Open,
+    #[default]
     Closed,
+    /// The door should come to rest at the given open fraction, strictly between `0.0` and `1.0`.
+    PartiallyOpen(f32),
 }
 
-impl Default for DoorGoal {
-    fn default() -> Self {
-        DoorGoal::Closed
+impl PartialEq<DoorState> for DoorGoal {
+    fn eq(&self, other: &DoorState) -> bool {
+        other == self
     }
 }
 
-impl PartialEq<DoorState> for DoorGoal {
-    fn eq(&self, other: &DoorState) -> bool {
+impl DoorGoal {
+    /// Get the goal of a door asked to open by the given fraction.
+    ///
+    /// A fraction of `0.0` or below is `Closed` and `1.0` or above is `Open`, matching
+    /// `DoorState::at_rest`. A fraction that is not a number closes the door.
+    pub fn at_fraction(open_fraction: f32) -> Self {
+        if open_fraction.is_nan() || open_fraction <= 0.0 {
+            DoorGoal::Closed
+        } else if open_fraction >= 1.0 {
+            DoorGoal::Open
+        } else {
+            DoorGoal::PartiallyOpen(open_fraction)
+        }
+    }
+
+    /// The open fraction the door rests at once it reaches this goal.
+    pub fn open_fraction(&self) -> f32 {
+        match self {
+            DoorGoal::Open => 1.0,
+            DoorGoal::Closed => 0.0,
+            DoorGoal::PartiallyOpen(open_fraction) => *open_fraction,
+        }
+    }
+
+    /// The state of a door at rest at this goal.
+    pub fn resting_state(&self) -> DoorState {
         match self {
-            DoorGoal::Open => other == &DoorState::Open,
-            DoorGoal::Closed => other == &DoorState::Closed,
+            DoorGoal::Open => DoorState::Open,
+            DoorGoal::Closed => DoorState::Closed,
+            DoorGoal::PartiallyOpen(open_fraction) => DoorState::PartiallyOpen(*open_fraction),
+        }
+    }
+}
+
+/// A component to store the side a door is opened from.
+///
+/// `Front` swings a left hinged door towards positive angles, `Back` towards negative angles.
+#[derive(Component, Clone, Copy, PartialEq, Default)]
+pub enum DoorSide {
+    #[default]
+    Front,
+    Back,
+}
+
+impl DoorSide {
+    /// The sign of the swing when opened from this side.
+    pub fn signum(&self) -> f32 {
+        match self {
+            DoorSide::Front => 1.0,
+            DoorSide::Back => -1.0,
         }
     }
 }
@@ -155,241 +1001,1186 @@ impl PartialEq<DoorState> for DoorGoal {
 #[derive(Component)]
 pub struct DoorJoint;
 
+/// A component to mark the leaves spawned as children of a composite door.
+///
+/// Each leaf is a single door sharing the composite door's name.
+#[derive(Component)]
+pub struct DoorLeaf;
+
+/// The character between a door name and a leaf suffix, as in `"door_1#left"`.
+pub const DOOR_LEAF_SEPARATOR: char = '#';
+
+/// A component to tell the two leaves of a composite door apart.
+///
+/// A single leaf can be addressed by suffixing the door name with `#left` or `#right`, while the
+/// plain door name still addresses both leaves.
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub enum DoorLeafSide {
+    Left,
+    Right,
+}
+
+impl DoorLeafSide {
+    /// The suffix that addresses this leaf.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            DoorLeafSide::Left => "left",
+            DoorLeafSide::Right => "right",
+        }
+    }
+
+    /// Get the leaf side named by a suffix.
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "left" => Some(DoorLeafSide::Left),
+            "right" => Some(DoorLeafSide::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Get the door entity a joint belongs to, given the entity the joint is parented to.
+///
+/// Joints of composite doors are parented to a leaf, which is a child of the door entity.
+fn door_root(door_entity: Entity, leaf_queries: &Query<&Parent, With<DoorLeaf>>) -> Entity {
+    leaf_queries
+        .get(door_entity)
+        .map_or(door_entity, |parent| parent.get())
+}
+
+/// A system parameter to resolve the door entities a door event is addressed to.
+#[derive(SystemParam)]
+pub(crate) struct DoorEventTargets<'w, 's> {
+    registry: Res<'w, DoorRegistry>,
+    door_queries: Query<'w, 's, Entity, (With<DoorProperties>, Without<DoorLeaf>)>,
+    group_queries: Query<'w, 's, (Entity, &'static DoorGroup)>,
+    children_queries: Query<'w, 's, &'static Children>,
+    leaf_side_queries: Query<'w, 's, &'static DoorLeafSide>,
+}
+
+impl DoorEventTargets<'_, '_> {
+    /// Get the door entities a door event is addressed to.
+    ///
+    /// A name with a leaf suffix resolves to that leaf of the door. The entity takes precedence
+    /// over the name, the name over the group, and the group over addressing every door.
+    pub(crate) fn resolve(&self, door_request: &DoorEvent) -> Vec<Entity> {
+        match (door_request.entity, &door_request.name, &door_request.group) {
+            (Some(door_entity), ..) => vec![door_entity],
+            (None, Some(name), _) => self.resolve_name(name),
+            (None, None, Some(group)) => self
+                .group_queries
+                .iter()
+                .filter(|(_, door_group)| &door_group.0 == group)
+                .map(|(door_entity, _)| door_entity)
+                .collect(),
+            (None, None, None) if door_request.all => self.door_queries.iter().collect(),
+            (None, None, None) => Vec::new(),
+        }
+    }
+
+    /// Get every door with the given name, or the given leaf of every door with the name before
+    /// its leaf suffix.
+    pub(crate) fn resolve_name(&self, name: &str) -> Vec<Entity> {
+        let doors = self.registry.doors(name);
+        if !doors.is_empty() {
+            return doors.to_vec();
+        }
+        let Some((door_name, suffix)) = name.rsplit_once(DOOR_LEAF_SEPARATOR) else {
+            return Vec::new();
+        };
+        let Some(side) = DoorLeafSide::from_suffix(suffix) else {
+            return Vec::new();
+        };
+        self.registry
+            .doors(door_name)
+            .iter()
+            .filter_map(|door_entity| self.children_queries.get(*door_entity).ok())
+            .flat_map(|children| children.iter())
+            .filter(|child| self.leaf_side_queries.get(**child) == Ok(&side))
+            .copied()
+            .collect()
+    }
+}
+
+/// A resource to hold the door events refused by systems running before `update_door_goal`.
+///
+/// `update_door_goal` skips these events and empties the list once it has read them.
+#[derive(Resource, Default)]
+pub(crate) struct RefusedDoorEvents(pub(crate) Vec<EventId<DoorEvent>>);
+
+/// A resource to scale the speed of every door at once.
+///
+/// It multiplies into the speed of each door, so `0.5` plays every door in slow motion and `2.0`
+/// fast-forwards them. Negative multipliers are treated as `0.0`.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct DoorSpeedMultiplier(pub f32);
+
+impl Default for DoorSpeedMultiplier {
+    fn default() -> Self {
+        DoorSpeedMultiplier(1.0)
+    }
+}
+
+/// A resource to configure the door plugin.
+///
+/// Insert it before adding `BevyDoorPlugin` to change the systems the plugin adds, otherwise the
+/// plugin inserts the default configuration.
+#[derive(Resource, Clone)]
+pub struct DoorPluginConfig {
+    /// The speed of doors without a speed of their own, in units or radians per second.
+    pub default_speed: f32,
+    /// Whether doors may share a name. Duplicate names panic when they may not.
+    pub allow_duplicate_names: bool,
+    /// Whether to warn about doors sharing a name when they may.
+    pub warn_on_duplicate_names: bool,
+    /// Whether to update `DoorOpenCount`, `DoorOpenDuration` and the `DoorAnalyticsSummary`.
+    pub enable_analytics: bool,
+    /// Whether to send `DoorNavmeshCut` events, with the `navmesh` feature.
+    pub enable_navmesh_events: bool,
+}
+
+impl Default for DoorPluginConfig {
+    fn default() -> Self {
+        DoorPluginConfig {
+            default_speed: DEFAULT_DOOR_SPEED,
+            allow_duplicate_names: true,
+            warn_on_duplicate_names: true,
+            enable_analytics: true,
+            enable_navmesh_events: true,
+        }
+    }
+}
+
 /// A Bevy plugin for doors.
-pub struct BevyDoorPlugin;
+///
+/// With `strict_mode` set, doors that violate their `DoorSizeConstraint` panic in debug builds.
+#[derive(Clone, Default)]
+pub struct BevyDoorPlugin {
+    pub strict_mode: bool,
+}
+
+/// The system sets of the door plugin, run in this order in `Update`.
+///
+/// Order your own systems against them instead of the door systems, for example to move a physics
+/// body `.after(DoorSystemSet::Movement)` or send door events `.in_set(DoorSystemSet::Request)`.
+/// Door events sent after `Validation` skip the airlock checks and are not refused.
+#[derive(SystemSet, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DoorSystemSet {
+    /// Doors are spawned from their `DoorProperties` and registered by name.
+    Spawn,
+    /// Door events are sent, by the door triggers and by your own systems.
+    Request,
+    /// Door events are checked against locks and airlocks, and refused ones are marked.
+    Validation,
+    /// Door events are turned into joint goals.
+    GoalUpdate,
+    /// Door joints move towards their goals.
+    Movement,
+    /// Door openings are counted and timed.
+    Analytics,
+}
 
 impl Plugin for BevyDoorPlugin {
     fn build(&self, app: &mut App) {
+        let config = app
+            .world_mut()
+            .get_resource_or_insert_with(DoorPluginConfig::default)
+            .clone();
         app.add_event::<DoorEvent>();
-        app.add_systems(Update, spawn_door);
-        app.add_systems(Update, update_door_goal);
-        app.add_systems(Update, update_door_movement);
+        app.add_event::<DoorStateChanged>();
+        app.add_event::<DoorLockEvent>();
+        app.add_event::<DoorStuckRecoveredEvent>();
+        app.add_event::<DoorCloneEvent>();
+        app.add_event::<DoorThemeChanged>();
+        app.add_event::<DespawnDoorEvent>();
+        app.add_event::<DoorSoundEvent>();
+        app.add_event::<DoorButtonPressed>();
+        app.add_event::<DoorSensorTriggered>();
+        #[cfg(feature = "navmesh")]
+        app.add_event::<DoorNavmeshCut>();
+        app.register_type::<DoorProperties>();
+        app.register_type::<DoorDimensions>();
+        app.register_type::<DoorType>();
+        app.register_type::<HingeSide>();
+        app.register_type::<SwingDirection>();
+        app.register_type::<DoorState>();
+        app.register_type::<DoorGoal>();
+        app.register_type::<DoorLock>();
+        app.register_type::<DoorGroup>();
+        app.register_type::<DoorAnimationCurve>();
+        app.register_type::<DoorAutoClose>();
+        app.register_type::<AutoOpen>();
+        app.register_type::<AccessLevel>();
+        app.register_type::<DoorStuckTimeout>();
+        app.register_type::<DoorEventLog>();
+        app.init_resource::<SignalBus>();
+        app.init_resource::<DoorTheme>();
+        app.init_resource::<DoorRegistry>();
+        app.init_resource::<RefusedDoorEvents>();
+        app.init_resource::<DoorAnalyticsSummary>();
+        app.init_resource::<SimulationClock>();
+        app.init_resource::<DoorSpeedMultiplier>();
+        app.init_resource::<DoorSafetyMode>();
+        app.init_resource::<DoorCallbacks>();
+        app.insert_resource(DoorStrictMode(self.strict_mode));
+        app.configure_sets(
+            Update,
+            (
+                DoorSystemSet::Spawn,
+                DoorSystemSet::Request,
+                DoorSystemSet::Validation,
+                DoorSystemSet::GoalUpdate,
+                DoorSystemSet::Movement,
+                DoorSystemSet::Analytics,
+            )
+                .chain(),
+        );
+        app.add_systems(PostStartup, validate_door_constraints);
+        app.add_systems(Startup, load_floor_plan);
+        app.add_systems(Update, spawn_door.in_set(DoorSystemSet::Spawn));
+        app.add_systems(
+            Update,
+            resize::rebuild_resized_doors
+                .in_set(DoorSystemSet::Spawn)
+                .after(spawn_door),
+        );
+        #[cfg(feature = "rapier")]
+        app.add_systems(Update, attach_door_colliders.after(spawn_door));
+        #[cfg(feature = "rapier")]
+        app.add_systems(
+            Update,
+            update_rapier_obstruction_sensors.before(update_door_movement),
+        );
+        app.add_systems(
+            Update,
+            deregister_door
+                .in_set(DoorSystemSet::Spawn)
+                .before(register_door)
+                .before(register_door_joints),
+        );
+        app.add_systems(Update, register_door.in_set(DoorSystemSet::Spawn));
+        app.add_systems(
+            Update,
+            register_door_joints
+                .in_set(DoorSystemSet::Spawn)
+                .after(spawn_door),
+        );
+        app.add_systems(Update, update_door_locks.in_set(DoorSystemSet::Validation));
+        app.add_systems(Update, update_simulation_clock);
+        app.add_systems(
+            Update,
+            apply_door_schedules
+                .in_set(DoorSystemSet::Request)
+                .after(update_simulation_clock),
+        );
+        app.add_systems(Update, update_door_goal.in_set(DoorSystemSet::GoalUpdate));
+        app.add_systems(
+            Update,
+            enforce_access_control
+                .in_set(DoorSystemSet::Validation)
+                .after(update_door_locks)
+                .before(enforce_airlock_interlock),
+        );
+        app.add_systems(
+            Update,
+            enforce_airlock_interlock
+                .in_set(DoorSystemSet::Validation)
+                .after(update_door_locks),
+        );
+        app.add_systems(
+            Update,
+            apply_door_safety_mode
+                .in_set(DoorSystemSet::GoalUpdate)
+                .after(update_door_goal),
+        );
+        app.add_systems(Update, update_door_movement.in_set(DoorSystemSet::Movement));
+        app.add_systems(
+            Update,
+            detect_stuck_doors
+                .in_set(DoorSystemSet::Movement)
+                .after(update_door_movement),
+        );
+        if config.enable_analytics {
+            app.add_systems(
+                Update,
+                update_door_analytics.in_set(DoorSystemSet::Analytics),
+            );
+        }
+        app.add_systems(
+            Update,
+            update_door_clearance
+                .after(update_door_movement)
+                .after(detect_stuck_doors),
+        );
+        #[cfg(feature = "navmesh")]
+        if config.enable_navmesh_events {
+            app.add_systems(Update, update_door_navmesh_cuts.after(update_door_movement));
+        }
+        app.add_systems(
+            Update,
+            update_door_status
+                .after(update_door_movement)
+                .after(detect_stuck_doors),
+        );
+        app.add_systems(
+            Update,
+            update_bi_fold_joints
+                .after(update_door_movement)
+                .after(detect_stuck_doors),
+        );
+        app.add_systems(
+            Update,
+            update_garage_sections
+                .after(update_door_movement)
+                .after(detect_stuck_doors),
+        );
+        app.add_systems(Update, clone_door);
+        app.add_systems(Update, despawn_door.after(register_door));
+        app.add_systems(Update, apply_door_theme);
+        app.add_systems(
+            Update,
+            restart_auto_close
+                .in_set(DoorSystemSet::GoalUpdate)
+                .before(update_door_goal),
+        );
+        app.add_systems(Update, update_auto_close.in_set(DoorSystemSet::Request));
+        app.add_systems(Update, update_auto_open.in_set(DoorSystemSet::Request));
+        app.add_systems(
+            Update,
+            update_proximity_door_triggers.in_set(DoorSystemSet::Request),
+        );
+        app.add_systems(Update, process_door_signals.in_set(DoorSystemSet::Request));
+        app.add_systems(
+            Update,
+            update_door_sensors.before(open_doors_on_sensor_trigger),
+        );
+        app.add_systems(
+            Update,
+            open_doors_on_sensor_trigger.in_set(DoorSystemSet::Request),
+        );
+        app.add_systems(
+            Update,
+            publish_door_state_signals.after(update_door_movement),
+        );
+        app.add_systems(Update, run_door_callbacks.after(DoorSystemSet::Movement));
     }
 }
 
 /// A system to spawn doors.
 ///
-/// The condition for spawning doors is when the door properties are added.
+/// The condition for spawning doors is when the door properties are added. Non-finite swing
+/// values and dimensions are replaced with their defaults, and panel sizes are clamped to
+/// `MIN_DOOR_DIMENSION`, before anything is spawned.
 fn spawn_door(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    theme: Res<DoorTheme>,
+    config: Res<DoorPluginConfig>,
     mut queries: Query<
-        (Entity, &DoorProperties, &mut Transform, &DoorDimensions),
+        (
+            Entity,
+            &mut DoorProperties,
+            &mut DoorDimensions,
+            Option<&DoorGlass>,
+            Option<&DoorMaterial>,
+            Option<&DoorMeshOverride>,
+            Has<DoorLeaf>,
+        ),
         Added<DoorProperties>,
     >,
 ) {
     // spawn a parent and a controller
-    for (entity, properties, transform, dimensions) in queries.iter_mut() {
+    for (entity, mut properties, mut dimensions, glass, door_material, mesh_override, is_leaf) in
+        queries.iter_mut()
+    {
+        properties.sanitize();
+        let name = properties.name.clone();
+        if properties.speed.is_none() {
+            properties.speed = Some(config.default_speed.abs().max(MIN_DOOR_SPEED));
+        }
+        // Leaves are spawned under their composite door, so they must not expand into leaves again
+        if is_leaf && properties.door_type.is_composite() {
+            log::warn!("Door {} has a composite leaf, skipping it", name);
+            continue;
+        }
+        dimensions.sanitize(&name);
+        if let DoorType::RollUp = properties.door_type {
+            // A roll up door cannot rise above its own height
+            if properties.swing_value.abs() > dimensions.height {
+                log::warn!(
+                    "Door {} swing value {} is above its height, clamping to {}",
+                    name,
+                    properties.swing_value,
+                    dimensions.height
+                );
+                properties.swing_value = dimensions.height;
+            }
+            properties.swing_value = properties.swing_value.abs();
+        }
+        if let DoorType::Garage { sections } = properties.door_type {
+            if sections == 0 {
+                log::warn!("Door {} has no garage sections, using 1", name);
+                properties.door_type = DoorType::Garage { sections: 1 };
+            }
+            // A garage door always rises by its own height
+            properties.swing_value = dimensions.height;
+        }
+        if let DoorType::PocketDoor { .. } = properties.door_type {
+            // A pocket door always slides all the way into its pocket
+            properties.swing_value = dimensions.length * properties.swing_value.signum();
+        }
+        if let DoorType::Revolving { segments: 0 } = properties.door_type {
+            log::warn!(
+                "Door {} has no revolving segments, using {}",
+                name,
+                DEFAULT_REVOLVING_DOOR_SEGMENTS
+            );
+            properties.door_type = DoorType::Revolving {
+                segments: DEFAULT_REVOLVING_DOOR_SEGMENTS,
+            };
+        }
+        if let DoorType::BiFold { panels } = properties.door_type {
+            if panels != 2 && panels != 4 {
+                log::warn!(
+                    "Door {} has {} bi-fold panels, only 2 or 4 are supported",
+                    name,
+                    panels
+                );
+            }
+        }
+        let properties = properties.into_inner();
+        let dimensions = dimensions.into_inner();
+
         match properties.door_type {
             DoorType::DoubleSliding => {
-                commands.spawn(DoorBundle {
-                    door_properties: DoorProperties::new(
-                        properties.name.clone(),
-                        -properties.swing_value.clone().abs() / 2.0,
-                        DoorType::SingleSliding,
-                    ),
-                    door_dimensions: DoorDimensions::new(
-                        dimensions.length / 2.0,
-                        dimensions.height,
-                        dimensions.thickness,
-                    ),
-                    transform: transform.clone(),
-                    ..Default::default()
-                });
+                let left_leaf = commands
+                    .spawn(DoorBundle {
+                        door_properties: DoorProperties::new(
+                            properties.name.clone(),
+                            -properties.swing_value.abs() / 2.0,
+                            DoorType::SingleSliding,
+                        )
+                        .with_speed(properties.speed())
+                        .with_snap_tolerance(properties.snap_tolerance)
+                        .with_easing(properties.easing),
+                        door_dimensions: DoorDimensions::new(
+                            dimensions.length / 2.0,
+                            dimensions.height,
+                            dimensions.thickness,
+                        ),
+                        ..Default::default()
+                    })
+                    .insert((DoorLeaf, DoorLeafSide::Left))
+                    .id();
 
-                let mut door_transform = transform.clone();
-                door_transform.translation.x += dimensions.length / 2.0;
+                if let Some(glass) = glass {
+                    commands.entity(left_leaf).insert(glass.clone());
+                }
+                if let Some(door_material) = door_material {
+                    commands.entity(left_leaf).insert(door_material.clone());
+                }
+                if let Some(mesh_override) = mesh_override {
+                    commands.entity(left_leaf).insert(mesh_override.clone());
+                }
 
-                commands.spawn(DoorBundle {
-                    door_properties: DoorProperties::new(
-                        properties.name.clone(),
-                        properties.swing_value.clone().abs() / 2.0,
-                        DoorType::SingleSliding,
-                    ),
-                    door_dimensions: DoorDimensions::new(
-                        dimensions.length / 2.0,
-                        dimensions.height,
-                        dimensions.thickness,
-                    ),
-                    transform: door_transform,
-                    ..Default::default()
-                });
-            }
-            DoorType::SingleSwinging | DoorType::SingleSliding => {
-                let door = commands
-                    .spawn(PbrBundle {
-                        mesh: meshes.add(Cuboid::new(
-                            dimensions.length,
+                let right_leaf = commands
+                    .spawn(DoorBundle {
+                        door_properties: DoorProperties::new(
+                            properties.name.clone(),
+                            properties.swing_value.abs() / 2.0,
+                            DoorType::SingleSliding,
+                        )
+                        .with_speed(properties.speed())
+                        .with_snap_tolerance(properties.snap_tolerance)
+                        .with_easing(properties.easing),
+                        door_dimensions: DoorDimensions::new(
+                            dimensions.length / 2.0,
                             dimensions.height,
                             dimensions.thickness,
-                        )),
-                        material: materials.add(Color::srgb_u8(124, 144, 255)),
-                        transform: Transform::from_xyz(
-                            dimensions.length / 2.0,
-                            dimensions.height / 2.0,
-                            0.0,
                         ),
-                        ..default()
+                        transform: Transform::from_xyz(dimensions.length / 2.0, 0.0, 0.0),
+                        ..Default::default()
                     })
+                    .insert((DoorLeaf, DoorLeafSide::Right))
                     .id();
 
-                let joint = commands.spawn(PbrBundle::default()).id();
+                if let Some(glass) = glass {
+                    commands.entity(right_leaf).insert(glass.clone());
+                }
+                if let Some(door_material) = door_material {
+                    commands.entity(right_leaf).insert(door_material.clone());
+                }
+                if let Some(mesh_override) = mesh_override {
+                    commands.entity(right_leaf).insert(mesh_override.clone());
+                }
+
+                commands
+                    .entity(entity)
+                    .push_children(&[left_leaf, right_leaf]);
+            }
+            DoorType::SingleSwinging
+            | DoorType::SingleSliding
+            | DoorType::DoubleAction
+            | DoorType::Folding
+            | DoorType::BiFold { panels: 0..=2 }
+            | DoorType::Revolving { .. }
+            | DoorType::RollUp
+            | DoorType::PocketDoor { .. }
+            | DoorType::Garage { .. } => {
+                let override_material =
+                    mesh_override.and_then(|mesh_override| mesh_override.material.clone());
+                let material = match (override_material, glass, door_material) {
+                    (Some(material), _, _) => material,
+                    (None, Some(glass), _) => materials.add(glass.material(dimensions.thickness)),
+                    (None, None, Some(door_material)) => materials.add(door_material.panel_color),
+                    (None, None, None) => materials.add(theme.material()),
+                };
+
+                let joint = match mesh_override
+                    .and_then(|mesh_override| mesh_override.joint_mesh.clone())
+                {
+                    Some(joint_mesh) => commands
+                        .spawn(PbrBundle {
+                            mesh: joint_mesh,
+                            material: material.clone(),
+                            ..default()
+                        })
+                        .id(),
+                    None => commands.spawn(PbrBundle::default()).id(),
+                };
 
-                // Parent the child to the joint
-                commands.entity(joint).add_child(door);
+                // A right hinged door turns about the far end of its length
+                let (hinge, panel_offset) = if properties.hinged_right() {
+                    (dimensions.length, -dimensions.length / 2.0)
+                } else {
+                    (0.0, dimensions.length / 2.0)
+                };
+                // The joint moves onto the anchor and the panel back by as much, so the closed
+                // panel stays put
+                let anchor_offset = properties.hinge_anchor_offset();
+                commands.entity(joint).insert(Transform::from_translation(
+                    Vec3::new(hinge, 0.0, 0.0) + anchor_offset,
+                ));
+
+                match properties.door_type {
+                    DoorType::Folding | DoorType::BiFold { .. } => spawn_folding_panels(
+                        &mut commands,
+                        &mut meshes,
+                        material,
+                        dimensions,
+                        joint,
+                    ),
+                    DoorType::Garage { sections } => spawn_garage_sections(
+                        &mut commands,
+                        &mut meshes,
+                        material,
+                        dimensions,
+                        sections,
+                        joint,
+                    ),
+                    DoorType::Revolving { segments } => spawn_revolving_wings(
+                        &mut commands,
+                        &mut meshes,
+                        material,
+                        dimensions,
+                        segments,
+                        joint,
+                    ),
+                    _ => {
+                        let door = commands
+                            .spawn(PbrBundle {
+                                mesh: match mesh_override {
+                                    Some(mesh_override) => mesh_override.panel_mesh.clone(),
+                                    None => meshes.add(Cuboid::new(
+                                        dimensions.length,
+                                        dimensions.height,
+                                        dimensions.thickness,
+                                    )),
+                                },
+                                material,
+                                transform: Transform::from_translation(
+                                    Vec3::new(panel_offset, dimensions.height / 2.0, 0.0)
+                                        - anchor_offset,
+                                ),
+                                ..default()
+                            })
+                            .id();
+
+                        if let Some(handle_color) =
+                            door_material.and_then(|door_material| door_material.handle_color)
+                        {
+                            spawn_door_handle(
+                                &mut commands,
+                                &mut meshes,
+                                materials.add(handle_color),
+                                dimensions,
+                                properties.hinged_right(),
+                                door,
+                            );
+                        }
+
+                        // Parent the child to the joint
+                        commands.entity(joint).add_child(door);
+                    }
+                }
                 commands.entity(joint).insert(DoorJoint);
                 commands.entity(joint).insert(DoorState::default());
                 commands.entity(joint).insert(DoorGoal::default());
+                commands.entity(joint).insert(DoorOpenProgress::default());
+                commands
+                    .entity(joint)
+                    .insert(DoorAnimationProgress::default());
+                commands.entity(joint).insert(DoorVelocity::default());
+                if let DoorType::DoubleAction = properties.door_type {
+                    commands.entity(joint).insert(DoorSide::default());
+                }
                 commands.entity(entity).add_child(joint);
+
+                if let DoorType::PocketDoor { wall_thickness } = properties.door_type {
+                    spawn_door_pocket(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        dimensions,
+                        wall_thickness,
+                        properties.swing_value,
+                        entity,
+                    );
+                }
             }
-            DoorType::DoubleSwinging => {
-                commands.spawn(DoorBundle {
-                    door_properties: DoorProperties::new(
-                        properties.name.clone(),
-                        properties.swing_value.clone(),
-                        DoorType::SingleSwinging,
-                    ),
-                    door_dimensions: DoorDimensions::new(
-                        dimensions.length / 2.0,
-                        dimensions.height,
-                        dimensions.thickness,
-                    ),
-                    transform: transform.clone(),
-                    ..Default::default()
-                });
+            DoorType::DoubleSwinging | DoorType::DoubleFolding | DoorType::BiFold { .. } => {
+                let leaf_type = match properties.door_type {
+                    DoorType::DoubleFolding => DoorType::Folding,
+                    DoorType::BiFold { .. } => DoorType::BiFold { panels: 2 },
+                    _ => DoorType::SingleSwinging,
+                };
 
-                let mut door_transform = transform.clone();
-                door_transform.translation.x += dimensions.length;
-                door_transform.rotation = Quat::from_rotation_y(std::f32::consts::PI);
+                let left_leaf = commands
+                    .spawn(DoorBundle {
+                        door_properties: DoorProperties::new(
+                            properties.name.clone(),
+                            properties.directed_swing_value(),
+                            leaf_type,
+                        )
+                        .with_speed(properties.speed())
+                        .with_snap_tolerance(properties.snap_tolerance)
+                        .with_anchor_offset(properties.anchor_offset)
+                        .with_easing(properties.easing),
+                        door_dimensions: DoorDimensions::new(
+                            dimensions.length / 2.0,
+                            dimensions.height,
+                            dimensions.thickness,
+                        ),
+                        ..Default::default()
+                    })
+                    .insert((DoorLeaf, DoorLeafSide::Left))
+                    .id();
 
-                commands.spawn(DoorBundle {
-                    door_properties: DoorProperties::new(
-                        properties.name.clone(),
-                        -properties.swing_value.clone(),
-                        DoorType::SingleSwinging,
-                    ),
-                    door_dimensions: DoorDimensions::new(
-                        dimensions.length / 2.0,
-                        dimensions.height,
-                        dimensions.thickness,
-                    ),
-                    transform: door_transform,
-                    ..Default::default()
-                });
+                if let Some(glass) = glass {
+                    commands.entity(left_leaf).insert(glass.clone());
+                }
+                if let Some(door_material) = door_material {
+                    commands.entity(left_leaf).insert(door_material.clone());
+                }
+                if let Some(mesh_override) = mesh_override {
+                    commands.entity(left_leaf).insert(mesh_override.clone());
+                }
+
+                // The right leaf is mirrored so both leaves hinge on the outer jambs
+                let right_leaf = commands
+                    .spawn(DoorBundle {
+                        door_properties: DoorProperties::new(
+                            properties.name.clone(),
+                            -properties.directed_swing_value(),
+                            leaf_type,
+                        )
+                        .with_speed(properties.speed())
+                        .with_snap_tolerance(properties.snap_tolerance)
+                        .with_anchor_offset(properties.anchor_offset)
+                        .with_easing(properties.easing),
+                        door_dimensions: DoorDimensions::new(
+                            dimensions.length / 2.0,
+                            dimensions.height,
+                            dimensions.thickness,
+                        ),
+                        transform: Transform::from_xyz(dimensions.length, 0.0, 0.0)
+                            .with_rotation(Quat::from_rotation_y(std::f32::consts::PI)),
+                        ..Default::default()
+                    })
+                    .insert((DoorLeaf, DoorLeafSide::Right))
+                    .id();
+
+                if let Some(glass) = glass {
+                    commands.entity(right_leaf).insert(glass.clone());
+                }
+                if let Some(door_material) = door_material {
+                    commands.entity(right_leaf).insert(door_material.clone());
+                }
+                if let Some(mesh_override) = mesh_override {
+                    commands.entity(right_leaf).insert(mesh_override.clone());
+                }
+
+                commands
+                    .entity(entity)
+                    .push_children(&[left_leaf, right_leaf]);
             }
         }
+
+        if dimensions.frame_thickness > 0.0 {
+            let frame_color = door_material
+                .and_then(|door_material| door_material.frame_color)
+                .unwrap_or(DEFAULT_DOOR_FRAME_COLOR);
+            spawn_door_frame(
+                &mut commands,
+                &mut meshes,
+                materials.add(frame_color),
+                dimensions,
+                entity,
+            );
+        }
     }
 }
 
 /// A system to update the door goal based on the door event.
+///
+/// Events addressed by name are resolved to a door entity through the `DoorRegistry`, and events
+/// addressed by group to every door in that `DoorGroup`. Only the joints below those door entities
+/// are visited. Joints of locked doors ignore requests that would open them, and every door ignores
+/// requests against the goal of the `DoorSafetyMode`.
 fn update_door_goal(
+    time: Res<Time>,
     mut door_requests: EventReader<DoorEvent>,
+    mut refused_events: ResMut<RefusedDoorEvents>,
+    safety_mode: Res<DoorSafetyMode>,
+    targets: DoorEventTargets,
     door_property_queries: Query<&DoorProperties, With<DoorProperties>>,
-    mut door_goal_quries: Query<(&Parent, &DoorState, &mut DoorGoal), With<DoorJoint>>,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    lock_queries: Query<&DoorLock>,
+    children_queries: Query<&Children>,
+    mut event_log_queries: Query<&mut DoorEventLog>,
+    mut door_goal_quries: Query<
+        (
+            Entity,
+            &Parent,
+            &DoorState,
+            &mut DoorGoal,
+            Option<&mut DoorSide>,
+        ),
+        With<DoorJoint>,
+    >,
 ) {
-    for door_request in door_requests.read() {
-        for (parent, state, mut goal) in door_goal_quries.iter_mut() {
+    for (door_request, event_id) in door_requests.read_with_id() {
+        if refused_events.0.contains(&event_id) {
+            continue;
+        }
+        let door_entities = targets.resolve(door_request);
+        if door_entities.is_empty() {
+            debug!(
+                "Door {:?} not found",
+                door_request.name.as_ref().or(door_request.group.as_ref())
+            );
+            continue;
+        }
+        let joints: Vec<Entity> = door_entities
+            .iter()
+            .flat_map(|door_entity| children_queries.iter_descendants(*door_entity))
+            .filter(|child| door_goal_quries.contains(*child))
+            .collect();
+
+        let mut logged_doors = Vec::new();
+
+        for joint in joints {
+            let (_, parent, state, mut goal, side) = door_goal_quries.get_mut(joint).unwrap();
             let door_entity = parent.get();
 
             let properties = door_property_queries
                 .get(door_entity)
                 .expect("Door properties not found");
 
-            if properties.name != door_request.name {
+            let root = door_root(door_entity, &leaf_queries);
+
+            let requested_goal = match door_request.action {
+                DoorAction::Open => DoorGoal::Open,
+                // Revolving doors have no partial position, so they open fully
+                DoorAction::OpenTo(_)
+                    if matches!(properties.door_type, DoorType::Revolving { .. }) =>
+                {
+                    DoorGoal::Open
+                }
+                DoorAction::OpenTo(fraction) => DoorGoal::at_fraction(fraction),
+                DoorAction::Close => DoorGoal::Closed,
+                // Locks are handled by `update_door_locks`
+                DoorAction::Lock | DoorAction::Unlock => continue,
+                DoorAction::Toggle => match *state {
+                    DoorState::Closed
+                    | DoorState::Closing
+                    | DoorState::PartiallyOpen(_)
+                    | DoorState::Obstructed => DoorGoal::Open,
+                    DoorState::Open | DoorState::Opening => DoorGoal::Closed,
+                    // A blocked door turns back from the way it was heading
+                    DoorState::Blocked if *goal == DoorGoal::Closed => DoorGoal::Open,
+                    DoorState::Blocked => DoorGoal::Closed,
+                },
+            };
+
+            if safety_mode
+                .forced_goal()
+                .is_some_and(|forced_goal| forced_goal != requested_goal)
+            {
+                debug!("Door {} is held by {:?}", properties.name, *safety_mode);
+                continue;
+            }
+
+            // Composite doors log a command once, not once per leaf
+            if !logged_doors.contains(&root) {
+                logged_doors.push(root);
+                if let Ok(mut event_log) = event_log_queries.get_mut(root) {
+                    event_log.push(time.elapsed(), DoorEventKind::Command(requested_goal));
+                }
+            }
+
+            // A moving door turns around from where it is on any request for another goal
+            let accepted = match door_request.action {
+                DoorAction::Toggle => true,
+                _ => match (requested_goal, *state) {
+                    (
+                        requested_goal,
+                        DoorState::Opening | DoorState::Closing | DoorState::Blocked,
+                    ) => requested_goal != *goal,
+                    (goal, DoorState::PartiallyOpen(_)) => goal != *state,
+                    // An obstructed door is already closing, but it can be opened again
+                    (goal, DoorState::Obstructed) => goal != DoorGoal::Closed,
+                    (DoorGoal::Open, state) => state == DoorState::Closed,
+                    (DoorGoal::Closed, state) => state == DoorState::Open,
+                    (DoorGoal::PartiallyOpen(_), state) => {
+                        state == DoorState::Closed || state == DoorState::Open
+                    }
+                },
+            };
+
+            if !accepted {
+                continue;
+            }
+
+            if requested_goal != DoorGoal::Closed
+                && door_locked(door_entity, &leaf_queries, &lock_queries)
+            {
+                debug!("Door {} is locked", properties.name);
                 continue;
             }
 
-            match door_request.goal {
+            match requested_goal {
                 DoorGoal::Open => {
                     // Open the door
-                    if *state == DoorState::Closed {
-                        log::info!("Opening door {}", properties.name);
-                        *goal = DoorGoal::Open;
+                    log::info!("Opening door {}", properties.name);
+                    *goal = DoorGoal::Open;
+                    if let Some(mut side) = side {
+                        *side = door_request.side;
                     }
                 }
                 DoorGoal::Closed => {
                     // Close the door
-                    if *state == DoorState::Open {
-                        log::info!("Closing door {}", properties.name);
-                        *goal = DoorGoal::Closed;
+                    log::info!("Closing door {}", properties.name);
+                    *goal = DoorGoal::Closed;
+                }
+                DoorGoal::PartiallyOpen(fraction) => {
+                    // Move the door to its partial position
+                    log::info!("Opening door {} to {}", properties.name, fraction);
+                    *goal = requested_goal;
+                    if let Some(mut side) = side {
+                        *side = door_request.side;
                     }
                 }
             }
         }
     }
+    refused_events.0.clear();
 }
 
 /// A system to update the door movement based on the door goal.
+///
+/// Doors advance their animation progress at `speed` units or radians per second of travel, scaled
+/// by the `DoorSpeedMultiplier`, and the `DoorAnimationCurve` maps the progress onto the door
+/// position. Doors snap onto their goal once the animation ends or they are within their snap
+/// tolerance or two frames of travel of it, so a large frame delta never overshoots.
 fn update_door_movement(
+    time: Res<Time>,
+    speed_multiplier: Res<DoorSpeedMultiplier>,
+    mut state_changes: EventWriter<DoorStateChanged>,
+    mut sound_events: EventWriter<DoorSoundEvent>,
     door_property_queries: Query<&DoorProperties, With<DoorProperties>>,
-    mut queries: Query<(&Parent, &mut Transform, &mut DoorState, &DoorGoal), With<DoorGoal>>,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    curve_queries: Query<&DoorAnimationCurve>,
+    obstruction_queries: Query<&DoorObstruction>,
+    sensor_queries: Query<&ObstructionSensor>,
+    paused_queries: Query<&DoorPaused>,
+    obstacles: DoorObstacles,
+    mut event_log_queries: Query<&mut DoorEventLog>,
+    mut queries: Query<
+        (
+            Entity,
+            &Parent,
+            &mut Transform,
+            &mut DoorState,
+            &DoorGoal,
+            Option<&DoorSide>,
+            &mut DoorOpenProgress,
+            &mut DoorAnimationProgress,
+            Option<&mut DoorVelocity>,
+        ),
+        With<DoorGoal>,
+    >,
 ) {
-    for (parent, mut transform, mut state, goal) in queries.iter_mut() {
-        if *goal == *state {
-            continue;
-        }
+    let mut logged_states = Vec::new();
+    let delta_seconds = time.delta_seconds() * speed_multiplier.0.max(0.0);
 
+    for (
+        joint,
+        parent,
+        mut transform,
+        mut state,
+        goal,
+        side,
+        mut progress,
+        mut animation,
+        mut velocity,
+    ) in queries.iter_mut()
+    {
         let door_entity = parent.get();
+        if door_paused(joint, door_entity, &leaf_queries, &paused_queries) {
+            if let Some(velocity) = velocity.as_mut() {
+                velocity.set_if_neq(DoorVelocity::default());
+            }
+            continue;
+        }
+        let start_transform = *transform;
 
         let properties = door_property_queries
             .get(door_entity)
             .expect("Door properties not found");
+        let root = door_root(door_entity, &leaf_queries);
 
-        debug!("Moving door {}", properties.name);
+        // A closing door waits for its obstruction to clear before it carries on
+        let closing = *goal == DoorGoal::Closed
+            && (*state == DoorState::Closing || *state == DoorState::Obstructed);
+        let obstructed = closing && door_obstructed(root, &obstruction_queries, &sensor_queries);
 
-        match properties.door_type {
-            DoorType::SingleSliding => match goal {
-                DoorGoal::Closed => {
-                    if transform.translation.x.abs() <= 0.02 {
-                        transform.translation.x = 0.0;
-                        *state = DoorState::Closed;
-                    } else {
-                        *state = DoorState::Closing;
-                        transform.translation.x += -0.01 * properties.swing_value.signum();
-                    }
-                }
-                DoorGoal::Open => {
-                    if transform.translation.x.abs() >= properties.swing_value.abs() {
-                        transform.translation.x = properties.swing_value;
-                        *state = DoorState::Open;
-                    } else {
-                        *state = DoorState::Opening;
-                        transform.translation.x += 0.01 * properties.swing_value.signum();
-                    }
-                }
-            },
-            DoorType::SingleSwinging => match goal {
-                DoorGoal::Closed => {
-                    if transform.rotation.to_euler(EulerRot::ZYX).1.abs() <= 0.02 {
-                        transform.rotation = Quat::from_xyzw(0.0, 0.0, 0.0, 1.0);
-                        *state = DoorState::Closed;
-                    } else {
-                        *state = DoorState::Closing;
-                        transform.rotate(Quat::from_rotation_y(
-                            -0.01 * properties.swing_value.signum(),
-                        ));
-                    }
+        let (new_state, open_fraction) = match properties.door_type {
+            _ if obstructed => (Some(DoorState::Obstructed), progress.0),
+            DoorType::Revolving { segments } => revolve_door(
+                properties,
+                segments,
+                *state,
+                *goal,
+                &mut transform,
+                delta_seconds,
+            ),
+            _ => {
+                let Some(mut position) = door_position(&properties.door_type, &transform) else {
+                    continue;
+                };
+                let open_position = door_open_position(properties, side);
+                let curve =
+                    door_animation_curve(door_entity, properties, &leaf_queries, &curve_queries);
+                let mut new_state = None;
+
+                // A door at rest may have been moved by other systems, so pick up from where it is
+                if *state != DoorState::Opening && *state != DoorState::Closing {
+                    animation.0 = curve.progress_at(door_open_fraction(position, open_position));
                 }
-                DoorGoal::Open => {
+
+                if *goal != *state {
                     debug!(
-                        "Moving door {:?}",
-                        transform.rotation.to_euler(EulerRot::ZYX)
+                        "Moving door {} ({:?}) from {:?} to {:?}",
+                        properties.name, properties.door_type, *state, *goal
                     );
-                    if transform.rotation.to_euler(EulerRot::ZYX).1.abs()
-                        >= properties.swing_value.abs()
+
+                    let target = goal.open_fraction() * open_position;
+                    let target_progress = curve.progress_at(goal.open_fraction());
+                    let opening = target_progress > animation.0;
+                    let start_position = position;
+                    let start_progress = animation.0;
+
+                    let travel = open_position.abs();
+                    let step = if travel > 0.0 {
+                        properties.speed() * delta_seconds / travel
+                    } else {
+                        1.0
+                    };
+                    animation.0 = if opening {
+                        (animation.0 + step).min(target_progress)
+                    } else {
+                        (animation.0 - step).max(target_progress)
+                    };
+                    position = curve.sample(animation.0) * open_position;
+
+                    // Doors snap within two frames of travel, so the snap scales with the speed
+                    // and the frame rate
+                    let snap_tolerance = properties
+                        .snap_tolerance
+                        .max(properties.speed() * delta_seconds * 2.0);
+                    new_state = if animation.0 == target_progress
+                        || (target - position).abs() <= snap_tolerance
                     {
-                        transform.rotation = Quat::from_rotation_y(properties.swing_value);
-                        *state = DoorState::Open;
+                        position = target;
+                        animation.0 = target_progress;
+                        Some(goal.resting_state())
+                    } else if opening {
+                        Some(DoorState::Opening)
                     } else {
-                        *state = DoorState::Opening;
-                        transform.rotate(Quat::from_rotation_y(
-                            0.01 * properties.swing_value.signum(),
-                        ));
+                        Some(DoorState::Closing)
+                    };
+                    debug_assert!(
+                        position.is_finite(),
+                        "Door {} moved to a non-finite position",
+                        properties.name
+                    );
+
+                    // A door that would run into an obstacle stays where it is until it clears
+                    let mut moved_transform = *transform;
+                    set_door_position(&properties.door_type, &mut moved_transform, position);
+                    if obstacles.blocked(door_entity, properties, &transform, &moved_transform) {
+                        position = start_position;
+                        animation.0 = start_progress;
+                        new_state = Some(DoorState::Blocked);
+                    } else {
+                        *transform = moved_transform;
                     }
                 }
-            },
-            _ => {}
+
+                (new_state, door_open_fraction(position, open_position))
+            }
+        };
+
+        if let Some(new_state) = new_state.filter(|new_state| new_state != &*state) {
+            // Leaves of a composite door moving together log their state change once
+            if !logged_states.contains(&(root, new_state)) {
+                logged_states.push((root, new_state));
+                if let Ok(mut event_log) = event_log_queries.get_mut(root) {
+                    event_log.push(time.elapsed(), DoorEventKind::StateChanged(new_state));
+                }
+                state_changes.send(DoorStateChanged {
+                    door_entity: root,
+                    name: properties.name.clone(),
+                    old_state: *state,
+                    new_state,
+                });
+                for kind in DoorSoundKind::for_transition(*state, new_state) {
+                    sound_events.send(DoorSoundEvent {
+                        name: properties.name.clone(),
+                        kind,
+                    });
+                }
+            }
+            *state = new_state;
+        }
+
+        if progress.0 != open_fraction {
+            progress.0 = open_fraction;
+        }
+
+        // The velocity is over real time, so a speed multiplier shows up in it
+        if let Some(velocity) = velocity.as_mut() {
+            velocity.set_if_neq(DoorVelocity::between(
+                &start_transform,
+                &transform,
+                time.delta_seconds(),
+            ));
+        }
+    }
+}
+
+/// Get the position of a door joint along its travel.
+///
+/// Sliding doors travel along x and roll up and garage doors along y in units, swinging doors
+/// rotate about y in radians.
+fn door_position(door_type: &DoorType, transform: &Transform) -> Option<f32> {
+    match door_type {
+        DoorType::SingleSliding | DoorType::PocketDoor { .. } => Some(transform.translation.x),
+        DoorType::RollUp | DoorType::Garage { .. } => Some(transform.translation.y),
+        DoorType::SingleSwinging
+        | DoorType::DoubleAction
+        | DoorType::Folding
+        | DoorType::BiFold { .. }
+        | DoorType::Revolving { .. } => Some(transform.rotation.to_euler(EulerRot::YXZ).0),
+        _ => None,
+    }
+}
+
+/// Set the position of a door joint along its travel.
+fn set_door_position(door_type: &DoorType, transform: &mut Transform, position: f32) {
+    match door_type {
+        DoorType::SingleSliding | DoorType::PocketDoor { .. } => transform.translation.x = position,
+        DoorType::RollUp | DoorType::Garage { .. } => transform.translation.y = position,
+        DoorType::SingleSwinging
+        | DoorType::DoubleAction
+        | DoorType::Folding
+        | DoorType::BiFold { .. }
+        | DoorType::Revolving { .. } => transform.rotation = Quat::from_rotation_y(position),
+        _ => {}
+    }
+}
+
+/// Get the normalized open fraction of a door joint at the given position.
+fn door_open_fraction(position: f32, open_position: f32) -> f32 {
+    if open_position == 0.0 {
+        return 0.0;
+    }
+    (position / open_position).clamp(0.0, 1.0)
+}
+
+/// Get the position of a door joint when it is fully open.
+fn door_open_position(properties: &DoorProperties, side: Option<&DoorSide>) -> f32 {
+    match properties.door_type {
+        // Double action doors swing away from the side they are opened from
+        DoorType::DoubleAction => {
+            properties.swing_value.abs()
+                * side.map_or(1.0, |side| side.signum())
+                * properties.hinge_side.signum()
+        }
+        DoorType::SingleSwinging => {
+            properties.directed_swing_value() * properties.hinge_side.signum()
         }
+        _ => properties.swing_value,
+    }
+}
+
+/// The parts of a door joint that snapping it onto a goal changes.
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct SnappedDoorJoint {
+    pub(crate) parent: &'static Parent,
+    pub(crate) transform: &'static mut Transform,
+    pub(crate) state: &'static mut DoorState,
+    pub(crate) goal: &'static mut DoorGoal,
+    pub(crate) side: Option<&'static DoorSide>,
+    pub(crate) progress: &'static mut DoorOpenProgress,
+    pub(crate) animation: &'static mut DoorAnimationProgress,
+}
+
+impl SnappedDoorJointItem<'_> {
+    /// Snap the joint straight onto the resting position of a goal, as locking a moving door or
+    /// recovering a stuck one does.
+    ///
+    /// Returns the state the joint was in, if it changed. Send a `DoorStateChanged` for it.
+    pub(crate) fn snap(
+        &mut self,
+        properties: &DoorProperties,
+        goal: DoorGoal,
+    ) -> Option<DoorState> {
+        let open_position = door_open_position(properties, self.side);
+        set_door_position(
+            &properties.door_type,
+            &mut self.transform,
+            goal.open_fraction() * open_position,
+        );
+        *self.goal = goal;
+        self.progress.0 = goal.open_fraction();
+        self.animation.0 = goal.open_fraction();
+
+        let old_state = *self.state;
+        *self.state = goal.resting_state();
+        (old_state != *self.state).then_some(old_state)
     }
 }
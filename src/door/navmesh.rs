@@ -0,0 +1,56 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::HashMap;
+
+/// A Bevy event sent when a door starts or stops blocking the path through it.
+///
+/// Navigation systems can use it to rebuild the region of their navmesh around the door. The door
+/// becomes passable once it is fully open, and blocks the path again once it is closed.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DoorNavmeshCut {
+    pub door_entity: Entity,
+    pub is_passable: bool,
+}
+
+/// A system to send navmesh cut events from door state changes.
+///
+/// Each door only sends an event when its passability changes, so the leaves of a double door
+/// reaching the same state do not send it twice.
+pub(crate) fn update_door_navmesh_cuts(
+    mut passable_doors: Local<HashMap<Entity, bool>>,
+    mut state_changes: EventReader<DoorStateChanged>,
+    mut navmesh_cuts: EventWriter<DoorNavmeshCut>,
+) {
+    for state_change in state_changes.read() {
+        let is_passable = match state_change.new_state {
+            DoorState::Open => true,
+            DoorState::Closed => false,
+            _ => continue,
+        };
+
+        if passable_doors.insert(state_change.door_entity, is_passable) == Some(is_passable) {
+            continue;
+        }
+        navmesh_cuts.send(DoorNavmeshCut {
+            door_entity: state_change.door_entity,
+            is_passable,
+        });
+    }
+}
@@ -0,0 +1,57 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to stop a door from closing onto something in its way.
+///
+/// Add it to a door entity next to the `DoorBundle`, pointing at a separate sensor entity with an
+/// `ObstructionSensor`. While the sensor reports an overlap, a closing door stops in
+/// `DoorState::Obstructed`, and it resumes closing once the overlap clears.
+#[derive(Component, Clone, Copy)]
+pub struct DoorObstruction {
+    pub sensor_entity: Entity,
+}
+
+impl DoorObstruction {
+    /// Create a new door obstruction component.
+    pub fn new(sensor_entity: Entity) -> Self {
+        DoorObstruction { sensor_entity }
+    }
+}
+
+/// A component for the sensor of a `DoorObstruction`.
+///
+/// Set `overlapping` from your physics engine's sensor or trigger collider. With the `rapier`
+/// feature it is kept up to date from the rapier `CollidingEntities` on the same entity.
+#[derive(Component, Clone, Copy, Default)]
+pub struct ObstructionSensor {
+    pub overlapping: bool,
+}
+
+/// Check whether the sensor of a door reports an obstruction.
+pub(crate) fn door_obstructed(
+    door_entity: Entity,
+    obstruction_queries: &Query<&DoorObstruction>,
+    sensor_queries: &Query<&ObstructionSensor>,
+) -> bool {
+    obstruction_queries
+        .get(door_entity)
+        .and_then(|obstruction| sensor_queries.get(obstruction.sensor_entity))
+        .is_ok_and(|sensor| sensor.overlapping)
+}
@@ -0,0 +1,39 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to freeze a door where it is.
+///
+/// Add it to a door entity, or to a single door joint, to stop the door moving without changing
+/// its goal. Requests sent while the door is paused still change its goal, and the door carries
+/// on towards its goal from where it stopped once the component is removed.
+#[derive(Component, Clone, Copy)]
+pub struct DoorPaused;
+
+/// Whether the door joint, its door or the composite door it belongs to is paused.
+pub(crate) fn door_paused(
+    joint: Entity,
+    door_entity: Entity,
+    leaf_queries: &Query<&Parent, With<DoorLeaf>>,
+    paused_queries: &Query<&DoorPaused>,
+) -> bool {
+    paused_queries.contains(joint)
+        || paused_queries.contains(door_entity)
+        || paused_queries.contains(door_root(door_entity, leaf_queries))
+}
@@ -0,0 +1,91 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to mark the wall pocket that a pocket door slides into.
+#[derive(Component)]
+pub struct DoorPocket;
+
+/// Spawn the wall pocket of a pocket door next to its door joint.
+///
+/// The pocket is a wall of the given thickness with a slot for the door panel, and it is as long
+/// and as tall as the door, on the side the door slides towards.
+pub(crate) fn spawn_door_pocket(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    dimensions: &DoorDimensions,
+    wall_thickness: f32,
+    swing_value: f32,
+    door_entity: Entity,
+) {
+    // The slot leaves a little clearance on both sides of the panel
+    let slot_thickness = dimensions.thickness * 1.5;
+    let skin_thickness = (wall_thickness - slot_thickness) / 2.0;
+    let skin_thickness = if skin_thickness > 0.0 {
+        skin_thickness
+    } else {
+        log::warn!(
+            "Pocket wall thickness {} is too thin for a door of thickness {}",
+            wall_thickness,
+            dimensions.thickness
+        );
+        dimensions.thickness / 2.0
+    };
+
+    let skin_mesh = meshes.add(Cuboid::new(
+        dimensions.length,
+        dimensions.height,
+        skin_thickness,
+    ));
+    let material = materials.add(Color::srgb_u8(200, 200, 200));
+
+    let skins: Vec<Entity> = [-1.0, 1.0]
+        .into_iter()
+        .map(|side: f32| {
+            commands
+                .spawn(PbrBundle {
+                    mesh: skin_mesh.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_xyz(
+                        0.0,
+                        dimensions.height / 2.0,
+                        side * (slot_thickness + skin_thickness) / 2.0,
+                    ),
+                    ..default()
+                })
+                .id()
+        })
+        .collect();
+
+    let pocket = commands
+        .spawn(PbrBundle {
+            transform: Transform::from_xyz(
+                dimensions.length / 2.0 + dimensions.length * swing_value.signum(),
+                0.0,
+                0.0,
+            ),
+            ..default()
+        })
+        .insert(DoorPocket)
+        .push_children(&skins)
+        .id();
+
+    commands.entity(door_entity).add_child(pocket);
+}
@@ -0,0 +1,94 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::HashSet;
+
+/// A component to tag entities for a `ProximityDoorTrigger`.
+#[derive(Component, Clone, PartialEq)]
+pub struct DoorTriggerTag(pub String);
+
+/// A component to open a door when an entity comes within range of it.
+///
+/// Add it to a door entity next to the `DoorBundle`. The door is opened once an entity is within
+/// `radius` of the door, and closed once every such entity has left. With a `target_tag`, only
+/// entities carrying a matching `DoorTriggerTag` count; without one, every entity with a
+/// `GlobalTransform` apart from the door itself does, including static scenery.
+#[derive(Component, Clone, Default)]
+pub struct ProximityDoorTrigger {
+    pub radius: f32,
+    pub target_tag: Option<String>,
+}
+
+impl ProximityDoorTrigger {
+    /// Create a new proximity trigger that reacts to any entity.
+    pub fn new(radius: f32) -> Self {
+        ProximityDoorTrigger {
+            radius,
+            target_tag: None,
+        }
+    }
+
+    /// Only react to entities tagged with the given `DoorTriggerTag`.
+    pub fn with_target_tag(mut self, target_tag: String) -> Self {
+        self.target_tag = Some(target_tag);
+        self
+    }
+
+    fn matches(&self, tag: Option<&DoorTriggerTag>) -> bool {
+        match (&self.target_tag, tag) {
+            (None, _) => true,
+            (Some(target_tag), Some(tag)) => *target_tag == tag.0,
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// A system to open doors when an entity comes within range and close them once all have left.
+pub(crate) fn update_proximity_door_triggers(
+    mut occupied_doors: Local<HashSet<Entity>>,
+    mut door_requests: EventWriter<DoorEvent>,
+    children_queries: Query<&Children>,
+    target_queries: Query<(Entity, &GlobalTransform, Option<&DoorTriggerTag>)>,
+    door_queries: Query<(
+        Entity,
+        &DoorProperties,
+        &GlobalTransform,
+        &ProximityDoorTrigger,
+    )>,
+) {
+    for (entity, properties, transform, trigger) in door_queries.iter() {
+        let door_position = transform.translation();
+        let door_parts: HashSet<Entity> = children_queries.iter_descendants(entity).collect();
+
+        let in_range = target_queries
+            .iter()
+            .any(|(target, target_transform, tag)| {
+                target != entity
+                    && !door_parts.contains(&target)
+                    && trigger.matches(tag)
+                    && target_transform.translation().distance(door_position) <= trigger.radius
+            });
+
+        if in_range && occupied_doors.insert(entity) {
+            door_requests.send(DoorEvent::open(properties.name.clone()));
+        } else if !in_range && occupied_doors.remove(&entity) {
+            door_requests.send(DoorEvent::close(properties.name.clone()));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use bevy_rapier3d::prelude::{Collider, CollidingEntities, RigidBody};
+
+/// A system to give newly spawned doors physics colliders.
+///
+/// Each door joint becomes a kinematic position based rigid body, and each panel below it gets a
+/// cuboid collider fitted to its mesh. The joint is still moved through its `Transform`, which
+/// rapier follows, so swinging panels rotate their colliders with the joint. Panels whose mesh is
+/// not loaded yet, such as a `DoorMeshOverride` from a GLTF asset still loading, get no collider.
+pub(crate) fn attach_door_colliders(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    children_queries: Query<&Children>,
+    joint_queries: Query<(Entity, &Parent), Added<DoorJoint>>,
+    door_property_queries: Query<&DoorProperties>,
+    panel_queries: Query<&Handle<Mesh>, Without<DoorJoint>>,
+) {
+    for (joint, parent) in joint_queries.iter() {
+        commands
+            .entity(joint)
+            .insert(RigidBody::KinematicPositionBased);
+
+        for panel in children_queries.iter_descendants(joint) {
+            let Ok(mesh) = panel_queries.get(panel) else {
+                continue;
+            };
+            let Some(aabb) = meshes.get(mesh).and_then(|mesh| mesh.compute_aabb()) else {
+                if let Ok(properties) = door_property_queries.get(parent.get()) {
+                    log::warn!(
+                        "Door {} panel mesh is not loaded, skipping its collider",
+                        properties.name
+                    );
+                }
+                continue;
+            };
+
+            let center = Vec3::from(aabb.center);
+            let half_extents = Vec3::from(aabb.half_extents);
+            let collider = Collider::cuboid(half_extents.x, half_extents.y, half_extents.z);
+            let collider = if center == Vec3::ZERO {
+                collider
+            } else {
+                Collider::compound(vec![(center, Quat::IDENTITY, collider)])
+            };
+            commands.entity(panel).insert(collider);
+        }
+    }
+}
+
+/// A system to update obstruction sensors from the entities their rapier collider touches.
+pub(crate) fn update_rapier_obstruction_sensors(
+    mut sensor_queries: Query<(&mut ObstructionSensor, &CollidingEntities)>,
+) {
+    for (mut sensor, colliding_entities) in sensor_queries.iter_mut() {
+        let overlapping = !colliding_entities.is_empty();
+        if sensor.overlapping != overlapping {
+            sensor.overlapping = overlapping;
+        }
+    }
+}
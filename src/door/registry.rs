@@ -0,0 +1,176 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use bevy::ecs::system::SystemParam;
+use std::collections::{HashMap, HashSet};
+
+/// A resource to look up doors by name.
+///
+/// It maps each name to the entities the `DoorBundle`s with that name were spawned on, in the
+/// order they were spawned, and each of those doors to its joints. The leaves of composite doors
+/// are not registered as doors of their own, but their joints count as joints of their door. It
+/// is kept up to date as doors are spawned and despawned.
+#[derive(Resource, Default)]
+pub struct DoorRegistry {
+    doors: HashMap<String, Vec<Entity>>,
+    joints: HashMap<Entity, Vec<Entity>>,
+    warned_names: HashSet<String>,
+}
+
+impl DoorRegistry {
+    /// Get the joints of every door with the given name.
+    ///
+    /// The joints are the entities that move when a door opens, double doors have one per leaf.
+    /// Nothing is returned for a name that no door has, or for doors that have not been spawned
+    /// yet.
+    pub fn get(&self, name: &str) -> Vec<Entity> {
+        self.doors(name)
+            .iter()
+            .filter_map(|door_entity| self.joints.get(door_entity))
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Get the entities of every door with the given name, in the order they were spawned.
+    pub fn doors(&self, name: &str) -> &[Entity] {
+        self.doors.get(name).map_or(&[], |doors| doors.as_slice())
+    }
+
+    /// Whether more than one door currently has the given name.
+    pub fn is_duplicate(&self, name: &str) -> bool {
+        self.doors(name).len() > 1
+    }
+
+    /// The number of registered doors.
+    pub fn len(&self) -> usize {
+        self.doors.values().map(|doors| doors.len()).sum()
+    }
+
+    /// Whether no doors are registered.
+    pub fn is_empty(&self) -> bool {
+        self.doors.is_empty()
+    }
+}
+
+/// A system parameter to find the joints of a door by name.
+///
+/// The joints are the entities that move when a door opens, double doors have one per leaf.
+#[derive(SystemParam)]
+pub struct DoorFinder<'w, 's> {
+    registry: Res<'w, DoorRegistry>,
+    children_queries: Query<'w, 's, &'static Children>,
+    joint_queries: Query<'w, 's, (), With<DoorJoint>>,
+}
+
+impl DoorFinder<'_, '_> {
+    /// Get the entity the first door with the given name was spawned on.
+    pub fn door_entity(&self, name: &str) -> Option<Entity> {
+        self.registry.doors(name).first().copied()
+    }
+
+    /// Get the entities of every door with the given name.
+    pub fn door_entities(&self, name: &str) -> &[Entity] {
+        self.registry.doors(name)
+    }
+
+    /// Iterate over the joints of every door with the given name.
+    ///
+    /// Nothing is returned for a name that no door has.
+    pub fn find_door(&self, name: &str) -> impl Iterator<Item = Entity> + '_ {
+        self.door_entities(name)
+            .iter()
+            .flat_map(|door_entity| self.children_queries.iter_descendants(*door_entity))
+            .filter(|child| self.joint_queries.contains(*child))
+    }
+}
+
+/// A system to add newly spawned doors to the door registry.
+///
+/// Door names are expected to be unique, but a door that reuses a name is registered next to the
+/// earlier doors, and events addressed by that name reach all of them. The leaves of composite
+/// doors share their door's name on purpose and are not registered.
+pub(crate) fn register_door(
+    config: Res<DoorPluginConfig>,
+    mut registry: ResMut<DoorRegistry>,
+    door_queries: Query<(Entity, &DoorProperties), (Added<DoorProperties>, Without<DoorLeaf>)>,
+) {
+    for (entity, properties) in door_queries.iter() {
+        let doors = registry.doors.entry(properties.name.clone()).or_default();
+        if doors.contains(&entity) {
+            continue;
+        }
+        doors.push(entity);
+        if doors.len() == 1 {
+            continue;
+        }
+        if !config.allow_duplicate_names {
+            panic!("Duplicate door name '{}'", properties.name);
+        }
+        // Warn once per name, however many doors end up sharing it
+        if registry.warned_names.insert(properties.name.clone()) && config.warn_on_duplicate_names {
+            log::warn!(
+                "Duplicate door name '{}', events by that name reach every door with it",
+                properties.name
+            );
+        }
+    }
+}
+
+/// A system to add newly spawned door joints to the door registry, under their door.
+pub(crate) fn register_door_joints(
+    mut registry: ResMut<DoorRegistry>,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    joint_queries: Query<(Entity, &Parent), Added<DoorJoint>>,
+) {
+    for (joint, parent) in joint_queries.iter() {
+        let root = door_root(parent.get(), &leaf_queries);
+        let joints = registry.joints.entry(root).or_default();
+        if !joints.contains(&joint) {
+            joints.push(joint);
+        }
+    }
+}
+
+/// A system to remove despawned doors and joints from the door registry.
+///
+/// Once a door is gone, the earlier doors sharing its name answer to it alone again. Doors whose
+/// properties were only replaced, as when they are rebuilt, stay registered.
+pub(crate) fn deregister_door(
+    mut registry: ResMut<DoorRegistry>,
+    mut removed_doors: RemovedComponents<DoorProperties>,
+    mut removed_joints: RemovedComponents<DoorJoint>,
+    door_queries: Query<(), With<DoorProperties>>,
+) {
+    for entity in removed_doors.read() {
+        if door_queries.contains(entity) {
+            continue;
+        }
+        registry.doors.retain(|_, doors| {
+            doors.retain(|door_entity| *door_entity != entity);
+            !doors.is_empty()
+        });
+        registry.joints.remove(&entity);
+    }
+    for joint in removed_joints.read() {
+        for joints in registry.joints.values_mut() {
+            joints.retain(|door_joint| *door_joint != joint);
+        }
+    }
+}
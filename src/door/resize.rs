@@ -0,0 +1,85 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::HashMap;
+
+/// A system to rebuild doors whose dimensions changed after they were spawned.
+///
+/// The joints, leaves, frame and pocket of a resized door are despawned and spawned again from its
+/// new dimensions, so the travel of sliding, pocket, roll up and garage doors follows the new size
+/// and both leaves of a double door are resized. A roll up door keeps rising by the same share of
+/// its height. The rebuilt door comes back closed. Only the
+/// dimensions of the door entity the `DoorBundle` was spawned on are followed, not those of its
+/// leaves.
+pub(crate) fn rebuild_resized_doors(
+    mut commands: Commands,
+    mut built_dimensions: Local<HashMap<Entity, DoorDimensions>>,
+    mut removed_doors: RemovedComponents<DoorDimensions>,
+    children_queries: Query<&Children>,
+    part_queries: Query<
+        (),
+        Or<(
+            With<DoorJoint>,
+            With<DoorLeaf>,
+            With<DoorFrame>,
+            With<DoorPocket>,
+        )>,
+    >,
+    door_queries: Query<
+        (Entity, &DoorProperties, &DoorDimensions),
+        (Changed<DoorDimensions>, Without<DoorLeaf>),
+    >,
+) {
+    for entity in removed_doors.read() {
+        built_dimensions.remove(&entity);
+    }
+
+    for (entity, properties, dimensions) in door_queries.iter() {
+        // Compare the dimensions the way spawning the door cleans them up
+        let mut dimensions = dimensions.clone();
+        dimensions.sanitize(&properties.name);
+        let Some(previous) = built_dimensions.insert(entity, dimensions.clone()) else {
+            continue;
+        };
+        if previous == dimensions {
+            continue;
+        }
+
+        log::info!("Door {} resized, rebuilding", properties.name);
+        if let Ok(children) = children_queries.get(entity) {
+            for child in children
+                .iter()
+                .filter(|child| part_queries.contains(**child))
+            {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        // The rise of a roll up door was clamped to its old height when it was spawned
+        let mut properties = properties.clone();
+        if properties.door_type == DoorType::RollUp && previous.height > 0.0 {
+            properties.swing_value *= dimensions.height / previous.height;
+        }
+
+        // Adding the properties again spawns the door from its new dimensions
+        commands
+            .entity(entity)
+            .remove::<DoorProperties>()
+            .insert(properties);
+    }
+}
@@ -0,0 +1,112 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The usual number of segments of a revolving door.
+pub const DEFAULT_REVOLVING_DOOR_SEGMENTS: u8 = 4;
+
+/// Spawn the hub and wings of a revolving door under its door joint.
+///
+/// The joint is moved to the center of the door, and one wing per segment reaches out to the
+/// door length.
+pub(crate) fn spawn_revolving_wings(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    dimensions: &DoorDimensions,
+    segments: u8,
+    joint: Entity,
+) {
+    let radius = dimensions.length / 2.0;
+    let wing_mesh = meshes.add(Cuboid::new(radius, dimensions.height, dimensions.thickness));
+
+    let hub = commands
+        .spawn(PbrBundle {
+            mesh: meshes.add(Cylinder::new(dimensions.thickness, dimensions.height)),
+            material: material.clone(),
+            transform: Transform::from_xyz(0.0, dimensions.height / 2.0, 0.0),
+            ..default()
+        })
+        .id();
+    commands.entity(joint).add_child(hub);
+
+    for wing in 0..segments.max(1) {
+        let rotation = Quat::from_rotation_y(wing as f32 * revolving_wing_spacing(segments));
+        let wing = commands
+            .spawn(PbrBundle {
+                mesh: wing_mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(
+                    rotation * Vec3::new(radius / 2.0, dimensions.height / 2.0, 0.0),
+                )
+                .with_rotation(rotation),
+                ..default()
+            })
+            .id();
+        commands.entity(joint).add_child(wing);
+    }
+
+    commands
+        .entity(joint)
+        .insert(Transform::from_xyz(radius, 0.0, 0.0));
+}
+
+/// The angle between two wings of a revolving door, in radians.
+fn revolving_wing_spacing(segments: u8) -> f32 {
+    std::f32::consts::TAU / segments.max(1) as f32
+}
+
+/// Turn a revolving door for one frame, returning its new state and open fraction.
+///
+/// An open door keeps turning at its swing value in radians per second. A closing door turns at
+/// `speed` radians per second to the nearest wing position and parks there.
+pub(crate) fn revolve_door(
+    properties: &DoorProperties,
+    segments: u8,
+    state: DoorState,
+    goal: DoorGoal,
+    transform: &mut Transform,
+    delta_seconds: f32,
+) -> (Option<DoorState>, f32) {
+    let angle = transform.rotation.to_euler(EulerRot::YXZ).0;
+
+    match goal {
+        DoorGoal::Open | DoorGoal::PartiallyOpen(_) => {
+            let angle = angle + properties.swing_value * delta_seconds;
+            transform.rotation = Quat::from_rotation_y(angle);
+            (Some(DoorState::Open), 1.0)
+        }
+        DoorGoal::Closed if state == DoorState::Closed => (None, 0.0),
+        DoorGoal::Closed => {
+            let step = properties.speed() * delta_seconds;
+            let spacing = revolving_wing_spacing(segments);
+            let target = (angle / spacing).round() * spacing;
+            let remaining = target - angle;
+
+            if remaining.abs() <= properties.snap_tolerance.max(step) {
+                transform.rotation = Quat::from_rotation_y(target);
+                (Some(DoorState::Closed), 0.0)
+            } else {
+                let angle = angle + step * remaining.signum();
+                transform.rotation = Quat::from_rotation_y(angle);
+                (Some(DoorState::Closing), 1.0)
+            }
+        }
+    }
+}
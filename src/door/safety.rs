@@ -0,0 +1,65 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A resource to put every door into a building's fail-safe mode.
+///
+/// In `FailOpen` every door drives open and requests to close doors are refused, as for a fire
+/// alarm, and `FailClosed` does the opposite, as for a security breach. Back in `Normal`, doors
+/// stay where the fail-safe mode left them and answer to their own requests again.
+///
+/// A fail-safe mode overrides `DoorLock`: locked doors open in `FailOpen`, and keep their lock so
+/// they can only be closed once the mode is back to `Normal`.
+#[derive(Resource, Clone, Copy, PartialEq, Debug, Default)]
+pub enum DoorSafetyMode {
+    #[default]
+    Normal,
+    FailOpen,
+    FailClosed,
+}
+
+impl DoorSafetyMode {
+    /// The goal every door is held at in this mode, if any.
+    pub fn forced_goal(&self) -> Option<DoorGoal> {
+        match self {
+            DoorSafetyMode::Normal => None,
+            DoorSafetyMode::FailOpen => Some(DoorGoal::Open),
+            DoorSafetyMode::FailClosed => Some(DoorGoal::Closed),
+        }
+    }
+}
+
+/// A system to drive every door to the goal of the fail-safe mode.
+pub(crate) fn apply_door_safety_mode(
+    safety_mode: Res<DoorSafetyMode>,
+    mut goal_queries: Query<&mut DoorGoal, With<DoorJoint>>,
+) {
+    let Some(forced_goal) = safety_mode.forced_goal() else {
+        return;
+    };
+    if safety_mode.is_changed() {
+        log::warn!("Doors are in {:?} mode", *safety_mode);
+    }
+
+    for mut goal in goal_queries.iter_mut() {
+        if *goal != forced_goal {
+            *goal = forced_goal;
+        }
+    }
+}
@@ -0,0 +1,264 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use bevy::ecs::entity::EntityHashMap;
+use bevy::scene::serde::SceneDeserializer;
+use serde::de::DeserializeSeed;
+use std::path::Path;
+
+/// A door entry in a RON door file.
+///
+/// The door type is given by name, for example `"single_swinging"` or `"garage"`. Door types with
+/// parameters read them from the optional `panels`, `segments`, `sections` and `wall_thickness`
+/// fields.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct DoorDescriptor {
+    pub name: String,
+    pub transform: Transform,
+    pub length: f32,
+    pub height: f32,
+    pub thickness: f32,
+    pub door_type: String,
+    pub swing_value: f32,
+    pub speed: Option<f32>,
+    pub panels: Option<u8>,
+    pub segments: Option<u8>,
+    pub sections: Option<u8>,
+    pub wall_thickness: Option<f32>,
+}
+
+impl DoorDescriptor {
+    /// Get the door type named by the descriptor, if it is known.
+    pub fn door_type(&self) -> Option<DoorType> {
+        let door_type = match self.door_type.as_str() {
+            "single_sliding" => DoorType::SingleSliding,
+            "double_sliding" => DoorType::DoubleSliding,
+            "single_swinging" => DoorType::SingleSwinging,
+            "double_swinging" => DoorType::DoubleSwinging,
+            "double_action" => DoorType::DoubleAction,
+            "folding" => DoorType::Folding,
+            "double_folding" => DoorType::DoubleFolding,
+            "bi_fold" => DoorType::BiFold {
+                panels: self.panels.unwrap_or(2),
+            },
+            "revolving" => DoorType::Revolving {
+                segments: self.segments.unwrap_or(DEFAULT_REVOLVING_DOOR_SEGMENTS),
+            },
+            "roll_up" => DoorType::RollUp,
+            "pocket_door" => DoorType::PocketDoor {
+                wall_thickness: self.wall_thickness.unwrap_or(self.thickness * 3.0),
+            },
+            "garage" => DoorType::Garage {
+                sections: self.sections.unwrap_or(4),
+            },
+            _ => return None,
+        };
+        Some(door_type)
+    }
+
+    /// Build the door bundle described by the descriptor, if its door type is known.
+    pub fn door_bundle(&self) -> Option<DoorBundle> {
+        let mut door_properties =
+            DoorProperties::new(self.name.clone(), self.swing_value, self.door_type()?);
+        if let Some(speed) = self.speed {
+            door_properties = door_properties.with_speed(speed);
+        }
+
+        Some(DoorBundle {
+            door_properties,
+            door_dimensions: DoorDimensions::new(self.length, self.height, self.thickness),
+            transform: self.transform,
+            ..Default::default()
+        })
+    }
+}
+
+/// Spawn the doors listed in a RON file, returning how many were spawned.
+///
+/// The file holds a list of `DoorDescriptor` entries. A missing or malformed file is logged and
+/// spawns nothing, and entries with an unknown door type are logged and skipped.
+pub fn spawn_doors_from_ron(commands: &mut Commands, path: impl AsRef<Path>) -> usize {
+    let path = path.as_ref();
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            log::error!("Unable to read door file {}: {}", path.display(), error);
+            return 0;
+        }
+    };
+
+    let descriptors: Vec<DoorDescriptor> = match ron::from_str(&contents) {
+        Ok(descriptors) => descriptors,
+        Err(error) => {
+            log::error!("Unable to parse door file {}: {}", path.display(), error);
+            return 0;
+        }
+    };
+
+    let mut spawned = 0;
+    for descriptor in descriptors.iter() {
+        match descriptor.door_bundle() {
+            Some(bundle) => {
+                commands.spawn(bundle);
+                spawned += 1;
+            }
+            None => {
+                log::warn!(
+                    "Door {} has unknown door type {}, skipping",
+                    descriptor.name,
+                    descriptor.door_type
+                );
+            }
+        }
+    }
+    log::info!("Spawned {} doors from {}", spawned, path.display());
+    spawned
+}
+
+/// A resource to hold the path of the Bevy scene the `save_doors` and `load_doors` systems use.
+///
+/// The scene is a `.scn.ron` file holding the `DoorProperties`, `DoorDimensions`, `DoorLock` and
+/// `Transform` of each door.
+#[derive(Resource, Clone, Debug)]
+pub struct DoorSceneLoader {
+    path: String,
+}
+
+impl DoorSceneLoader {
+    /// Create a new door scene loader resource.
+    pub fn new(path: &str) -> Self {
+        DoorSceneLoader {
+            path: path.to_string(),
+        }
+    }
+
+    /// Get the path of the door scene.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// Build a Bevy scene of every door in the world.
+///
+/// Only the entities the `DoorBundle`s were spawned on are saved. The leaves, joints and panels
+/// below them are spawned again from the door properties when the scene is loaded.
+pub fn door_scene(world: &World) -> DynamicScene {
+    let doors: Vec<Entity> = world
+        .iter_entities()
+        .filter(|entity| entity.contains::<DoorProperties>() && !entity.contains::<DoorLeaf>())
+        .map(|entity| entity.id())
+        .collect();
+
+    DynamicSceneBuilder::from_world(world)
+        .deny_all_resources()
+        .deny_all()
+        .allow::<DoorProperties>()
+        .allow::<DoorDimensions>()
+        .allow::<DoorLock>()
+        .allow::<Transform>()
+        .extract_entities(doors.into_iter())
+        .build()
+}
+
+/// Spawn the doors of a serialized door scene, returning how many were spawned.
+///
+/// A malformed scene is logged and spawns nothing.
+pub fn spawn_doors_from_scene(world: &mut World, contents: &str) -> usize {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let scene = {
+        let type_registry = type_registry.read();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &type_registry,
+        };
+        let mut deserializer = match ron::Deserializer::from_str(contents) {
+            Ok(deserializer) => deserializer,
+            Err(error) => {
+                log::error!("Unable to read door scene: {}", error);
+                return 0;
+            }
+        };
+        match scene_deserializer.deserialize(&mut deserializer) {
+            Ok(scene) => scene,
+            Err(error) => {
+                log::error!("Unable to parse door scene: {}", error);
+                return 0;
+            }
+        }
+    };
+
+    let mut entity_map = EntityHashMap::default();
+    if let Err(error) = scene.write_to_world(world, &mut entity_map) {
+        log::error!("Unable to spawn door scene: {}", error);
+        return 0;
+    }
+
+    // The scene only holds what the door needs to respawn, so fill in the rest of the bundle
+    for entity in entity_map.values() {
+        world.entity_mut(*entity).insert((
+            GlobalTransform::default(),
+            Visibility::default(),
+            InheritedVisibility::default(),
+            ViewVisibility::default(),
+        ));
+    }
+    entity_map.len()
+}
+
+/// A system to save every door to the scene at the `DoorSceneLoader` path.
+pub fn save_doors(world: &mut World) {
+    let Some(loader) = world.get_resource::<DoorSceneLoader>().cloned() else {
+        log::warn!("No DoorSceneLoader to save doors to");
+        return;
+    };
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let contents = match door_scene(world).serialize(&type_registry.read()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            log::error!("Unable to serialize door scene: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(&loader.path, contents) {
+        log::error!("Unable to write door scene {}: {}", loader.path, error);
+        return;
+    }
+    log::info!("Saved doors to {}", loader.path);
+}
+
+/// A system to spawn the doors of the scene at the `DoorSceneLoader` path.
+pub fn load_doors(world: &mut World) {
+    let Some(loader) = world.get_resource::<DoorSceneLoader>().cloned() else {
+        log::warn!("No DoorSceneLoader to load doors from");
+        return;
+    };
+
+    let contents = match std::fs::read_to_string(&loader.path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            log::error!("Unable to read door scene {}: {}", loader.path, error);
+            return;
+        }
+    };
+
+    let spawned = spawn_doors_from_scene(world, &contents);
+    log::info!("Spawned {} doors from {}", spawned, loader.path);
+}
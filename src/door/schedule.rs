@@ -0,0 +1,124 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::HashMap;
+
+/// The number of hours in a simulated day.
+pub const HOURS_PER_DAY: f32 = 24.0;
+
+/// A resource to hold the simulated time of day, in hours from midnight.
+///
+/// It follows `Time::elapsed_seconds`, one simulated hour per real hour, until `set_hours` is
+/// called; from then on it stays at the set time until it is set again.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct SimulationClock {
+    hours: f32,
+    manual: bool,
+}
+
+impl SimulationClock {
+    /// Get the time of day in hours, from 0.0 up to 24.0.
+    pub fn hours(&self) -> f32 {
+        self.hours
+    }
+
+    /// Set the time of day in hours, wrapping around midnight.
+    pub fn set_hours(&mut self, hours: f32) {
+        self.hours = hours.rem_euclid(HOURS_PER_DAY);
+        self.manual = true;
+    }
+}
+
+/// A component to lock a door outside of its opening hours.
+///
+/// Add it to a door entity next to the `DoorBundle`. The door is unlocked from `unlock_hour` and
+/// locked from `lock_hour` on the `SimulationClock`. A `lock_hour` before the `unlock_hour` keeps
+/// the door unlocked over midnight. A door that is open when it locks is also closed.
+#[derive(Component, Clone)]
+pub struct DoorSchedule {
+    pub unlock_hour: f32,
+    pub lock_hour: f32,
+}
+
+impl DoorSchedule {
+    /// Create a new door schedule component.
+    pub fn new(unlock_hour: f32, lock_hour: f32) -> Self {
+        DoorSchedule {
+            unlock_hour,
+            lock_hour,
+        }
+    }
+
+    /// Whether the door should be unlocked at the given time of day.
+    pub fn is_unlocked_at(&self, hours: f32) -> bool {
+        if self.unlock_hour <= self.lock_hour {
+            hours >= self.unlock_hour && hours < self.lock_hour
+        } else {
+            hours >= self.unlock_hour || hours < self.lock_hour
+        }
+    }
+}
+
+/// A system to advance the simulation clock with the elapsed time.
+pub(crate) fn update_simulation_clock(time: Res<Time>, mut clock: ResMut<SimulationClock>) {
+    if clock.manual {
+        return;
+    }
+    clock.hours = time.elapsed_seconds() / 3600.0 % HOURS_PER_DAY;
+}
+
+/// A system to lock and unlock doors on their schedule.
+///
+/// Doors are only locked or unlocked when their schedule changes, so a door can still be locked or
+/// unlocked by hand in between.
+pub(crate) fn apply_door_schedules(
+    mut scheduled_locks: Local<HashMap<Entity, bool>>,
+    clock: Res<SimulationClock>,
+    mut door_requests: EventWriter<DoorEvent>,
+    children_queries: Query<&Children>,
+    joint_queries: Query<&DoorState, With<DoorJoint>>,
+    door_queries: Query<(Entity, &DoorProperties, &DoorSchedule, Option<&DoorLock>)>,
+) {
+    for (entity, properties, schedule, lock) in door_queries.iter() {
+        let locked = !schedule.is_unlocked_at(clock.hours);
+        let changed = match scheduled_locks.insert(entity, locked) {
+            Some(previously_locked) => previously_locked != locked,
+            // Bring a newly scheduled door in line with its schedule
+            None => lock.is_some_and(|lock| lock.locked) != locked,
+        };
+        if !changed {
+            continue;
+        }
+
+        if !locked {
+            log::info!("Door {} unlocked on schedule", properties.name);
+            door_requests.send(DoorEvent::addressed(entity, DoorAction::Unlock));
+            continue;
+        }
+
+        log::info!("Door {} locked on schedule", properties.name);
+        door_requests.send(DoorEvent::addressed(entity, DoorAction::Lock));
+        let open = joint_queries
+            .iter_many(children_queries.iter_descendants(entity))
+            .any(|state| *state != DoorState::Closed);
+        if open {
+            door_requests.send(DoorEvent::close_entity(entity));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::{HashMap, HashSet};
+
+/// How far above or below a pressure plate an entity can be and still press it.
+const PRESSURE_PLATE_HEIGHT: f32 = 0.1;
+
+/// A enum to describe what triggers a `DoorSensor`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DoorSensorType {
+    /// Triggered by any entity coming within `range` of the door.
+    MotionDetector,
+    /// Triggered by any entity standing on a flat square plate at the door's feet, reaching
+    /// `range` from the door in each direction along the floor.
+    PressurePlate,
+    /// Triggered by a `DoorButtonPressed` event for the door.
+    Button,
+    /// Triggered by an entity with an `AccessLevel` coming within `range` of the door, if its
+    /// level is at least the `AccessLevel` of the door.
+    Keycard,
+}
+
+/// A component to trigger a door from game logic.
+///
+/// Add it to a door entity next to the `DoorBundle`. Each time the sensor is triggered it sends a
+/// `DoorSensorTriggered` event, and the door is opened on behalf of the entity that triggered it,
+/// so the door's `AccessLevel` applies to every type of sensor. Every entity with a
+/// `GlobalTransform` can trigger it once each time it comes into range, apart from doors, walls,
+/// lifts, stairs, windows and escalators and their parts, so the scenery around a sensor never
+/// triggers it. Nothing closes the door again, so pair the sensor with a `DoorAutoClose`.
+#[derive(Component, Clone, Copy)]
+pub struct DoorSensor {
+    pub sensor_type: DoorSensorType,
+    pub range: f32,
+}
+
+impl DoorSensor {
+    /// Create a new door sensor component.
+    pub fn new(sensor_type: DoorSensorType, range: f32) -> Self {
+        DoorSensor { sensor_type, range }
+    }
+}
+
+/// A Bevy event to press the button of a door with a `DoorSensorType::Button` sensor.
+#[derive(Event, Clone, Copy)]
+pub struct DoorButtonPressed {
+    pub door_entity: Entity,
+    pub pressed_by: Entity,
+}
+
+/// A Bevy event sent when a door sensor is triggered.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DoorSensorTriggered {
+    pub door_entity: Entity,
+    pub triggered_by: Entity,
+}
+
+/// The infrastructure entities whose parts never trigger a door sensor.
+type Infrastructure = Or<(
+    With<DoorProperties>,
+    With<WallProperties>,
+    With<LiftProperties>,
+    With<StairProperties>,
+    With<WindowProperties>,
+    With<EscalatorProperties>,
+)>;
+
+/// A system to trigger door sensors from the entities around them and from button presses.
+///
+/// An entity triggers a sensor once when it is first detected, and again only after it has left.
+pub(crate) fn update_door_sensors(
+    mut detected_entities: Local<HashMap<Entity, HashSet<Entity>>>,
+    mut button_presses: EventReader<DoorButtonPressed>,
+    mut sensor_events: EventWriter<DoorSensorTriggered>,
+    children_queries: Query<&Children>,
+    infrastructure_queries: Query<Entity, Infrastructure>,
+    target_queries: Query<(Entity, &GlobalTransform, Option<&AccessLevel>)>,
+    door_queries: Query<(Entity, &GlobalTransform, &DoorSensor, Option<&AccessLevel>)>,
+) {
+    for button_press in button_presses.read() {
+        let Ok((_, _, sensor, _)) = door_queries.get(button_press.door_entity) else {
+            continue;
+        };
+        if sensor.sensor_type == DoorSensorType::Button {
+            sensor_events.send(DoorSensorTriggered {
+                door_entity: button_press.door_entity,
+                triggered_by: button_press.pressed_by,
+            });
+        }
+    }
+
+    // Walls, floors and other doors in range are scenery, not visitors
+    let mut infrastructure_parts = HashSet::new();
+    if door_queries
+        .iter()
+        .any(|(_, _, sensor, _)| sensor.sensor_type != DoorSensorType::Button)
+    {
+        for root in infrastructure_queries.iter() {
+            infrastructure_parts.insert(root);
+            infrastructure_parts.extend(children_queries.iter_descendants(root));
+        }
+    }
+
+    for (entity, transform, sensor, required_level) in door_queries.iter() {
+        if sensor.sensor_type == DoorSensorType::Button {
+            continue;
+        }
+        let door_position = transform.translation();
+        let world_to_door = transform.affine().inverse();
+
+        let detected: HashSet<Entity> = target_queries
+            .iter()
+            .filter(|(target, target_transform, holder_level)| {
+                if infrastructure_parts.contains(target) {
+                    return false;
+                }
+                let target_position = target_transform.translation();
+                match sensor.sensor_type {
+                    DoorSensorType::MotionDetector => {
+                        target_position.distance(door_position) <= sensor.range
+                    }
+                    DoorSensorType::PressurePlate => {
+                        let local = world_to_door.transform_point3(target_position);
+                        local.x.abs() <= sensor.range
+                            && local.z.abs() <= sensor.range
+                            && local.y.abs() <= PRESSURE_PLATE_HEIGHT
+                    }
+                    DoorSensorType::Keycard => {
+                        target_position.distance(door_position) <= sensor.range
+                            && holder_level.is_some_and(|holder_level| {
+                                *holder_level >= required_level.copied().unwrap_or_default()
+                            })
+                    }
+                    DoorSensorType::Button => false,
+                }
+            })
+            .map(|(target, _, _)| target)
+            .collect();
+
+        let previous = detected_entities.remove(&entity).unwrap_or_default();
+        for target in detected.difference(&previous) {
+            sensor_events.send(DoorSensorTriggered {
+                door_entity: entity,
+                triggered_by: *target,
+            });
+        }
+        if !detected.is_empty() {
+            detected_entities.insert(entity, detected);
+        }
+    }
+}
+
+/// A system to open the doors whose sensors were triggered.
+pub(crate) fn open_doors_on_sensor_trigger(
+    mut sensor_events: EventReader<DoorSensorTriggered>,
+    mut door_requests: EventWriter<DoorEvent>,
+) {
+    for sensor_event in sensor_events.read() {
+        door_requests.send(
+            DoorEvent::open_entity(sensor_event.door_entity)
+                .with_requester(sensor_event.triggered_by),
+        );
+    }
+}
@@ -0,0 +1,106 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::collections::HashMap;
+
+/// A resource to hold the digital signals shared with external automation systems.
+#[derive(Resource, Default)]
+pub struct SignalBus {
+    pub signals: HashMap<u32, bool>,
+}
+
+impl SignalBus {
+    /// Get the value of a signal, signals that were never set are low.
+    pub fn get(&self, signal_id: u32) -> bool {
+        self.signals.get(&signal_id).copied().unwrap_or(false)
+    }
+
+    /// Set the value of a signal.
+    pub fn set(&mut self, signal_id: u32, value: bool) {
+        self.signals.insert(signal_id, value);
+    }
+}
+
+/// A component to connect a door to the signal bus.
+///
+/// A rising edge on the open or close signal sends the matching door event, and the state signal
+/// is high while the door is fully open.
+#[derive(Component, Clone)]
+pub struct DoorSignal {
+    pub open_signal_id: u32,
+    pub close_signal_id: u32,
+    pub state_signal_id: u32,
+}
+
+impl DoorSignal {
+    /// Create a new door signal component.
+    pub fn new(open_signal_id: u32, close_signal_id: u32, state_signal_id: u32) -> Self {
+        DoorSignal {
+            open_signal_id,
+            close_signal_id,
+            state_signal_id,
+        }
+    }
+}
+
+/// A system to send door events on rising edges of the door signals.
+pub(crate) fn process_door_signals(
+    signal_bus: Res<SignalBus>,
+    mut previous_signals: Local<HashMap<u32, bool>>,
+    mut door_requests: EventWriter<DoorEvent>,
+    door_signal_queries: Query<(Entity, &DoorSignal)>,
+) {
+    if !signal_bus.is_changed() {
+        return;
+    }
+
+    let rising = |signal_id: u32| -> bool {
+        signal_bus.get(signal_id) && !previous_signals.get(&signal_id).copied().unwrap_or(false)
+    };
+
+    for (entity, door_signal) in door_signal_queries.iter() {
+        if rising(door_signal.open_signal_id) {
+            door_requests.send(DoorEvent::open_entity(entity));
+        }
+        if rising(door_signal.close_signal_id) {
+            door_requests.send(DoorEvent::close_entity(entity));
+        }
+    }
+
+    *previous_signals = signal_bus.signals.clone();
+}
+
+/// A system to publish the door states onto the signal bus.
+///
+/// Signals are only written when they change, so the bus is not marked as changed every frame.
+pub(crate) fn publish_door_state_signals(
+    mut signal_bus: ResMut<SignalBus>,
+    children_queries: Query<&Children>,
+    door_signal_queries: Query<(Entity, &DoorSignal)>,
+    joint_queries: Query<&DoorState, With<DoorJoint>>,
+) {
+    for (entity, door_signal) in door_signal_queries.iter() {
+        let mut states = joint_queries.iter_many(children_queries.iter_descendants(entity));
+        let is_open = states.all(|state| *state == DoorState::Open);
+
+        if signal_bus.get(door_signal.state_signal_id) != is_open {
+            signal_bus.set(door_signal.state_signal_id, is_open);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A enum to describe the point of a door movement a `DoorSoundEvent` is sent at.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DoorSoundKind {
+    OpenStart,
+    OpenEnd,
+    CloseStart,
+    CloseEnd,
+}
+
+impl DoorSoundKind {
+    /// The sounds to play when a door joint moves from one state into another.
+    ///
+    /// A door that reaches its goal in a single frame both starts and ends its movement.
+    pub(crate) fn for_transition(old_state: DoorState, new_state: DoorState) -> Vec<Self> {
+        let moving = |state| state == DoorState::Opening || state == DoorState::Closing;
+        let start = match new_state {
+            DoorState::Opening => Some(DoorSoundKind::OpenStart),
+            DoorState::Closing => Some(DoorSoundKind::CloseStart),
+            // Snapped to a resting state without moving first
+            DoorState::Open if !moving(old_state) => Some(DoorSoundKind::OpenStart),
+            DoorState::Closed if !moving(old_state) => Some(DoorSoundKind::CloseStart),
+            _ => None,
+        };
+        let end = match (old_state, new_state) {
+            (_, DoorState::Open) => Some(DoorSoundKind::OpenEnd),
+            (_, DoorState::Closed) => Some(DoorSoundKind::CloseEnd),
+            (DoorState::Opening, DoorState::PartiallyOpen(_)) => Some(DoorSoundKind::OpenEnd),
+            (DoorState::Closing, DoorState::PartiallyOpen(_)) => Some(DoorSoundKind::CloseEnd),
+            _ => None,
+        };
+        start.into_iter().chain(end).collect()
+    }
+}
+
+/// A Bevy event sent when a door starts or finishes opening or closing, to hook sounds onto.
+///
+/// `OpenStart` and `CloseStart` are sent once, on the frame the door starts moving, including
+/// when it reverses or carries on closing after an obstruction clears. `OpenEnd` and `CloseEnd`
+/// are sent on the frame it comes to rest, or stops part way open. A double door sends a single
+/// event for both of its leaves when they move together.
+#[derive(Event, Clone)]
+pub struct DoorSoundEvent {
+    pub name: String,
+    pub kind: DoorSoundKind,
+}
@@ -0,0 +1,139 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component on the door entity summarising the state of all of its joints.
+///
+/// The `DoorState` of each joint is combined as follows, so double doors report a single state:
+/// - `Obstructed` if any joint is obstructed, then `Blocked` if any joint is blocked,
+/// - otherwise `Opening` if any joint is opening, then `Closing` if any joint is closing,
+/// - otherwise `Open` or `Closed` if every joint is,
+/// - otherwise `PartiallyOpen` at the average open fraction of the joints.
+///
+/// It is added to the door entity once the door is spawned and updated every frame.
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub struct DoorStatus {
+    state: DoorState,
+    open_fraction: f32,
+}
+
+impl Default for DoorStatus {
+    fn default() -> Self {
+        DoorStatus {
+            state: DoorState::Closed,
+            open_fraction: 0.0,
+        }
+    }
+}
+
+impl DoorStatus {
+    /// The combined state of the door.
+    pub fn state(&self) -> DoorState {
+        self.state
+    }
+
+    /// The average open fraction of the door joints.
+    pub fn open_fraction(&self) -> f32 {
+        self.open_fraction
+    }
+
+    /// Combine the states and open fractions of the joints of a door.
+    fn combine(joints: &[(DoorState, f32)]) -> Self {
+        let open_fraction =
+            joints.iter().map(|(_, fraction)| fraction).sum::<f32>() / joints.len() as f32;
+        let any = |state: DoorState| joints.iter().any(|(joint_state, _)| *joint_state == state);
+        let all = |state: DoorState| joints.iter().all(|(joint_state, _)| *joint_state == state);
+
+        let state = if any(DoorState::Obstructed) {
+            DoorState::Obstructed
+        } else if any(DoorState::Blocked) {
+            DoorState::Blocked
+        } else if any(DoorState::Opening) {
+            DoorState::Opening
+        } else if any(DoorState::Closing) {
+            DoorState::Closing
+        } else if all(DoorState::Open) {
+            DoorState::Open
+        } else if all(DoorState::Closed) {
+            DoorState::Closed
+        } else if let [(state, _)] = joints {
+            // A single joint keeps its own resting state
+            *state
+        } else {
+            DoorState::PartiallyOpen(open_fraction)
+        };
+
+        DoorStatus {
+            state,
+            open_fraction,
+        }
+    }
+}
+
+/// Get the combined state of the doors with the given name, without writing a query.
+///
+/// Doors sharing a name are combined like the joints of a single door. It returns `None` if there
+/// is no such door, or if none of them has been spawned yet.
+pub fn door_state(world: &World, name: &str) -> Option<DoorState> {
+    let statuses: Vec<(DoorState, f32)> = world
+        .get_resource::<DoorRegistry>()?
+        .doors(name)
+        .iter()
+        .filter_map(|door_entity| world.get::<DoorStatus>(*door_entity))
+        .map(|status| (status.state(), status.open_fraction()))
+        .collect();
+    if statuses.is_empty() {
+        return None;
+    }
+    Some(DoorStatus::combine(&statuses).state())
+}
+
+/// A system to mirror the state of the door joints onto the door entity.
+pub(crate) fn update_door_status(
+    mut commands: Commands,
+    children_queries: Query<&Children>,
+    joint_queries: Query<(&DoorState, &DoorOpenProgress), With<DoorJoint>>,
+    mut door_queries: Query<
+        (Entity, Option<&mut DoorStatus>),
+        (With<DoorProperties>, Without<DoorLeaf>),
+    >,
+) {
+    for (entity, status) in door_queries.iter_mut() {
+        let joints: Vec<(DoorState, f32)> = joint_queries
+            .iter_many(children_queries.iter_descendants(entity))
+            .map(|(state, progress)| (*state, progress.open_fraction()))
+            .collect();
+        if joints.is_empty() {
+            // The door has not been spawned yet
+            continue;
+        }
+
+        let new_status = DoorStatus::combine(&joints);
+        match status {
+            Some(mut status) => {
+                if *status != new_status {
+                    *status = new_status;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(new_status);
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// A component to recover doors that get stuck while opening or closing.
+///
+/// Add it to a door entity next to the `DoorBundle`.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct DoorStuckTimeout {
+    pub max_seconds_in_transition: f32,
+    pub elapsed: f32,
+}
+
+impl DoorStuckTimeout {
+    /// Create a new door stuck timeout component.
+    pub fn new(max_seconds_in_transition: f32) -> Self {
+        DoorStuckTimeout {
+            max_seconds_in_transition,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// A Bevy event sent when a stuck door has been snapped to a terminal state.
+#[derive(Event)]
+pub struct DoorStuckRecoveredEvent {
+    pub name: String,
+    pub goal: DoorGoal,
+}
+
+/// A system to snap doors that stay in a transitional state for too long.
+///
+/// The door is snapped to whichever of its open or closed positions is nearest, and a
+/// `DoorStateChanged` is sent for it. The leaves of a composite door are snapped together. Paused
+/// doors are left alone.
+pub(crate) fn detect_stuck_doors(
+    time: Res<Time>,
+    mut recovered_events: EventWriter<DoorStuckRecoveredEvent>,
+    mut state_changes: EventWriter<DoorStateChanged>,
+    children_queries: Query<&Children>,
+    leaf_queries: Query<&Parent, With<DoorLeaf>>,
+    mut door_queries: Query<(Entity, &DoorProperties, &mut DoorStuckTimeout)>,
+    door_property_queries: Query<&DoorProperties>,
+    paused_queries: Query<&DoorPaused>,
+    mut joint_queries: Query<SnappedDoorJoint, With<DoorJoint>>,
+) {
+    for (entity, properties, mut timeout) in door_queries.iter_mut() {
+        let joints: Vec<Entity> = children_queries
+            .iter_descendants(entity)
+            .filter(|child| joint_queries.contains(*child))
+            .collect();
+
+        // A paused door is held in place on purpose, so it is not stuck
+        let paused = paused_queries.contains(entity)
+            || children_queries
+                .iter_descendants(entity)
+                .any(|child| paused_queries.contains(child));
+        if paused {
+            continue;
+        }
+
+        let in_transition = joints.iter().any(|joint| {
+            let state = joint_queries.get(*joint).unwrap().state;
+            *state == DoorState::Opening || *state == DoorState::Closing
+        });
+
+        if !in_transition {
+            timeout.elapsed = 0.0;
+            continue;
+        }
+
+        timeout.elapsed += time.delta_seconds();
+        if timeout.elapsed <= timeout.max_seconds_in_transition {
+            continue;
+        }
+        timeout.elapsed = 0.0;
+
+        // Decide on the nearest terminal state for the door as a whole
+        let mut open_fraction = 0.0;
+        for joint in joints.iter() {
+            let snapped = joint_queries.get(*joint).unwrap();
+            let joint_properties = door_property_queries
+                .get(snapped.parent.get())
+                .expect("Door properties not found");
+
+            let position =
+                door_position(&joint_properties.door_type, snapped.transform).unwrap_or(0.0);
+            let open_position = door_open_position(joint_properties, snapped.side);
+            if open_position != 0.0 {
+                open_fraction += position / open_position / joints.len() as f32;
+            }
+        }
+
+        let recovered_goal = if open_fraction < 0.5 {
+            DoorGoal::Closed
+        } else {
+            DoorGoal::Open
+        };
+
+        let mut old_state = None;
+        for joint in joints.iter() {
+            let mut snapped = joint_queries.get_mut(*joint).unwrap();
+            let joint_properties = door_property_queries
+                .get(snapped.parent.get())
+                .expect("Door properties not found");
+            old_state = old_state.or(snapped.snap(joint_properties, recovered_goal));
+        }
+
+        log::warn!("Door {} was stuck, recovered", properties.name);
+        if let Some(old_state) = old_state {
+            state_changes.send(DoorStateChanged {
+                door_entity: door_root(entity, &leaf_queries),
+                name: properties.name.clone(),
+                old_state,
+                new_state: recovered_goal.resting_state(),
+            });
+        }
+        recovered_events.send(DoorStuckRecoveredEvent {
+            name: properties.name.clone(),
+            goal: recovered_goal,
+        });
+    }
+}
@@ -0,0 +1,1498 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+#[cfg(feature = "serde")]
+use bevy::ecs::system::RunSystemOnce;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+/// Build a headless app with the door plugin, stepping time by a fixed 16 ms per update.
+fn door_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.init_resource::<Assets<Mesh>>();
+    app.init_resource::<Assets<StandardMaterial>>();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        16,
+    )));
+    app.add_plugins(BevyDoorPlugin::default());
+    app
+}
+
+/// Spawn a single swinging door and step the app until it has been spawned.
+fn spawn_test_door(app: &mut App, swing_value: f32) -> Entity {
+    let door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                swing_value,
+                DoorType::SingleSwinging,
+            )
+            .with_speed(2.0),
+            door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+            ..Default::default()
+        })
+        .id();
+    step(app, 2);
+    door
+}
+
+fn step(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// Get the state and rotation about y of the door joint.
+fn joint(app: &mut App) -> (DoorState, f32) {
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<(&DoorState, &Transform), With<DoorJoint>>();
+    let (state, transform) = joint_query.single(app.world());
+    (*state, transform.rotation.to_euler(EulerRot::YXZ).0)
+}
+
+#[test]
+fn door_opens_to_its_swing_value() {
+    let mut app = door_app();
+    spawn_test_door(&mut app, 1.5);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 10);
+    assert_eq!(joint(&mut app).0, DoorState::Opening);
+
+    step(&mut app, 60);
+    let (state, angle) = joint(&mut app);
+    assert_eq!(state, DoorState::Open);
+    assert!((angle - 1.5).abs() < 1e-4, "door stopped at {}", angle);
+}
+
+#[test]
+fn door_closes_back_to_zero() {
+    let mut app = door_app();
+    spawn_test_door(&mut app, 1.5);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 70);
+    app.world_mut()
+        .send_event(DoorEvent::close("test_door".to_string()));
+    step(&mut app, 10);
+    assert_eq!(joint(&mut app).0, DoorState::Closing);
+
+    step(&mut app, 60);
+    let (state, angle) = joint(&mut app);
+    assert_eq!(state, DoorState::Closed);
+    assert!(angle.abs() < 1e-4, "door stopped at {}", angle);
+}
+
+#[test]
+fn closing_a_closed_door_does_nothing() {
+    let mut app = door_app();
+    spawn_test_door(&mut app, 1.5);
+
+    app.world_mut()
+        .send_event(DoorEvent::close("test_door".to_string()));
+    step(&mut app, 10);
+    let (state, angle) = joint(&mut app);
+    assert_eq!(state, DoorState::Closed);
+    assert_eq!(angle, 0.0);
+
+    let state_changes = app.world().resource::<Events<DoorStateChanged>>();
+    assert!(state_changes.is_empty());
+}
+
+#[test]
+fn duplicate_door_names_are_flagged() {
+    let mut app = door_app();
+    let first = spawn_test_door(&mut app, 1.5);
+    let second = spawn_test_door(&mut app, 1.5);
+
+    let registry = app.world().resource::<DoorRegistry>();
+    assert!(registry.is_duplicate("test_door"));
+    assert_eq!(registry.doors("test_door"), &[first, second]);
+    assert_eq!(registry.get("test_door").len(), 2);
+    assert_eq!(registry.len(), 2);
+
+    // Events by name reach every door with the name
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 60);
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<&DoorState, With<DoorJoint>>();
+    assert!(joint_query
+        .iter(app.world())
+        .all(|state| *state == DoorState::Open));
+
+    // Despawning the newer door leaves the earlier one registered
+    app.world_mut().entity_mut(second).despawn_recursive();
+    step(&mut app, 1);
+    let registry = app.world().resource::<DoorRegistry>();
+    assert!(!registry.is_duplicate("test_door"));
+    assert_eq!(registry.doors("test_door"), &[first]);
+    assert_eq!(registry.get("test_door").len(), 1);
+}
+
+#[test]
+fn config_default_speed_applies_to_doors_without_a_speed() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.init_resource::<Assets<Mesh>>();
+    app.init_resource::<Assets<StandardMaterial>>();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        16,
+    )));
+    app.insert_resource(DoorPluginConfig {
+        default_speed: 10.0,
+        ..Default::default()
+    });
+    app.add_plugins(BevyDoorPlugin::default());
+
+    let door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                1.5,
+                DoorType::SingleSwinging,
+            ),
+            door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+            ..Default::default()
+        })
+        .id();
+    let explicit_door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "explicit_door".to_string(),
+                1.5,
+                DoorType::SingleSwinging,
+            )
+            .with_speed(DEFAULT_DOOR_SPEED),
+            door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+            ..Default::default()
+        })
+        .id();
+    step(&mut app, 2);
+
+    let speed = |door| app.world().get::<DoorProperties>(door).unwrap().speed();
+    assert_eq!(speed(door), 10.0);
+    assert_eq!(speed(explicit_door), DEFAULT_DOOR_SPEED);
+}
+
+#[test]
+fn anchor_offset_moves_the_pivot_but_not_the_closed_panel() {
+    let mut app = door_app();
+    app.world_mut().spawn(DoorBundle {
+        door_properties: DoorProperties::new(
+            "test_door".to_string(),
+            1.5,
+            DoorType::SingleSwinging,
+        )
+        .with_anchor_offset(Vec3::new(0.5, 0.0, 0.0)),
+        door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+        ..Default::default()
+    });
+    step(&mut app, 2);
+
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<(&Transform, &Children), With<DoorJoint>>();
+    let (joint_transform, children) = joint_query.single(app.world());
+    let panel = app.world().get::<Transform>(children[0]).unwrap();
+    assert_eq!(joint_transform.translation, Vec3::new(0.5, 0.0, 0.0));
+    assert_eq!(
+        joint_transform.translation + panel.translation,
+        Vec3::new(0.5, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn door_open_callback_runs_once() {
+    #[derive(Resource, Default)]
+    struct Openings(u32);
+
+    let mut app = door_app();
+    app.init_resource::<Openings>();
+    spawn_test_door(&mut app, 1.5);
+    app.world_mut()
+        .commands()
+        .on_door_open("test_door", |mut openings: ResMut<Openings>| {
+            openings.0 += 1;
+        });
+
+    for _ in 0..2 {
+        app.world_mut()
+            .send_event(DoorEvent::open("test_door".to_string()));
+        step(&mut app, 70);
+        app.world_mut()
+            .send_event(DoorEvent::close("test_door".to_string()));
+        step(&mut app, 70);
+    }
+
+    assert_eq!(app.world().resource::<Openings>().0, 1);
+    assert!(app.world().resource::<DoorCallbacks>().is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn door_scene_round_trip() {
+    let path = std::env::temp_dir().join("bevy_infrastructure_door_scene.scn.ron");
+    let loader = DoorSceneLoader::new(path.to_str().unwrap());
+
+    let mut app = door_app();
+    app.register_type::<Transform>();
+    for (name, length) in [("front_door", 1.0), ("back_door", 1.6)] {
+        app.world_mut().spawn(DoorBundle {
+            door_properties: DoorProperties::new(name.to_string(), 1.5, DoorType::DoubleSwinging),
+            door_dimensions: DoorDimensions::new(length, 2.0, 0.05),
+            ..Default::default()
+        });
+    }
+    step(&mut app, 2);
+    app.insert_resource(loader.clone());
+    app.world_mut().run_system_once(save_doors);
+
+    let mut app = door_app();
+    app.register_type::<Transform>();
+    app.insert_resource(loader);
+    app.world_mut().run_system_once(load_doors);
+    step(&mut app, 2);
+    std::fs::remove_file(&path).unwrap();
+
+    let mut door_query = app
+        .world_mut()
+        .query_filtered::<(&DoorProperties, &DoorDimensions), Without<DoorLeaf>>();
+    let mut doors: Vec<(String, f32)> = door_query
+        .iter(app.world())
+        .map(|(properties, dimensions)| (properties.name().to_string(), dimensions.length))
+        .collect();
+    doors.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        doors,
+        vec![
+            ("back_door".to_string(), 1.6),
+            ("front_door".to_string(), 1.0)
+        ]
+    );
+
+    // Both leaves of each door are spawned again
+    let mut leaf_query = app.world_mut().query_filtered::<(), With<DoorLeaf>>();
+    assert_eq!(leaf_query.iter(app.world()).count(), 4);
+}
+
+#[test]
+fn zero_dimensions_are_clamped() {
+    let mut app = door_app();
+    let door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                1.5,
+                DoorType::DoubleSwinging,
+            ),
+            door_dimensions: DoorDimensions::new(0.0, -2.0, 0.0),
+            ..Default::default()
+        })
+        .id();
+    step(&mut app, 2);
+
+    let dimensions = app.world().get::<DoorDimensions>(door).unwrap();
+    assert_eq!(dimensions.length, MIN_DOOR_DIMENSION);
+    assert_eq!(dimensions.height, MIN_DOOR_DIMENSION);
+    assert_eq!(dimensions.thickness, MIN_DOOR_DIMENSION);
+
+    // Halving the clamped length for the leaves is clamped again
+    let mut leaf_query = app
+        .world_mut()
+        .query_filtered::<&DoorDimensions, With<DoorLeaf>>();
+    for leaf_dimensions in leaf_query.iter(app.world()) {
+        assert_eq!(leaf_dimensions.length, MIN_DOOR_DIMENSION);
+    }
+}
+
+/// Count the elements of each tag in an XML document, checking that every tag is closed in order.
+#[cfg(feature = "urdf")]
+fn count_xml_elements(xml: &str) -> std::collections::HashMap<String, usize> {
+    let mut counts = std::collections::HashMap::new();
+    let mut open_tags: Vec<String> = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let end = rest[start..].find('>').expect("Unclosed tag") + start;
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('?') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(open_tags.pop().as_deref(), Some(name), "Mismatched tag");
+            continue;
+        }
+        let name = tag.split_whitespace().next().unwrap().trim_end_matches('/');
+        *counts.entry(name.to_string()).or_insert(0) += 1;
+        if !tag.ends_with('/') {
+            open_tags.push(name.to_string());
+        }
+    }
+    assert!(open_tags.is_empty(), "Unclosed elements {:?}", open_tags);
+    counts
+}
+
+#[cfg(feature = "urdf")]
+#[test]
+fn doors_export_to_urdf() {
+    let mut app = door_app();
+    spawn_test_door(&mut app, 1.5);
+    app.world_mut().spawn(DoorBundle {
+        door_properties: DoorProperties::new(
+            "sliding_door".to_string(),
+            0.9,
+            DoorType::SingleSliding,
+        ),
+        door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+        ..Default::default()
+    });
+    app.world_mut().spawn(DoorBundle {
+        door_properties: DoorProperties::new(
+            "double_door".to_string(),
+            1.5,
+            DoorType::DoubleSwinging,
+        ),
+        door_dimensions: DoorDimensions::new(2.0, 2.0, 0.05),
+        ..Default::default()
+    });
+    step(&mut app, 2);
+
+    let urdf = export_doors_to_urdf(app.world());
+    let counts = count_xml_elements(&urdf);
+    assert_eq!(counts["robot"], 1);
+    // The world link and one panel link per joint
+    assert_eq!(counts["link"], 5);
+    assert_eq!(counts["joint"], 4);
+    assert_eq!(counts["limit"], 4);
+    assert_eq!(urdf.matches(r#"type="revolute""#).count(), 3);
+    assert_eq!(urdf.matches(r#"type="prismatic""#).count(), 1);
+    assert!(urdf.contains(r#"<joint name="double_door_left_joint""#));
+}
+
+#[test]
+fn door_velocity_follows_the_movement() {
+    let mut app = door_app();
+    spawn_test_door(&mut app, 1.5);
+    let mut velocity_query = app
+        .world_mut()
+        .query_filtered::<&DoorVelocity, With<DoorJoint>>();
+    assert!(!velocity_query.single(app.world()).is_moving());
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 10);
+    let velocity = *velocity_query.single(app.world());
+    assert_eq!(velocity.linear, Vec3::ZERO);
+    assert!((velocity.angular.y - 2.0).abs() < 1e-3);
+
+    step(&mut app, 60);
+    assert_eq!(joint(&mut app).0, DoorState::Open);
+    assert!(!velocity_query.single(app.world()).is_moving());
+}
+
+#[test]
+fn door_properties_builder_checks_the_swing_value() {
+    let builder = || {
+        DoorPropertiesBuilder::new()
+            .name("test_door".to_string())
+            .door_type(DoorType::SingleSwinging)
+    };
+
+    assert!(builder().swing_value(1.5).build().is_ok());
+    assert_eq!(
+        builder().swing_value(0.0).build().unwrap_err(),
+        DoorPropertiesError::InvalidSwingValue(0.0)
+    );
+    assert_eq!(
+        builder()
+            .swing_value(1.5)
+            .swing_direction(SwingDirection::Outward)
+            .build()
+            .unwrap_err(),
+        DoorPropertiesError::SwingDirectionMismatch(1.5, SwingDirection::Outward)
+    );
+    assert_eq!(
+        builder()
+            .name(String::new())
+            .swing_value(1.5)
+            .build()
+            .unwrap_err(),
+        DoorPropertiesError::EmptyName
+    );
+    assert_eq!(builder().build_unchecked().swing_value(), 0.0);
+}
+
+#[test]
+fn resized_double_door_rebuilds_both_leaves() {
+    let mut app = door_app();
+    let door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                1.5,
+                DoorType::DoubleSwinging,
+            ),
+            door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+            ..Default::default()
+        })
+        .id();
+    step(&mut app, 3);
+
+    app.world_mut()
+        .get_mut::<DoorDimensions>(door)
+        .unwrap()
+        .length = 2.0;
+    step(&mut app, 4);
+
+    let mut leaf_query = app
+        .world_mut()
+        .query_filtered::<&DoorDimensions, With<DoorLeaf>>();
+    let leaf_lengths: Vec<f32> = leaf_query
+        .iter(app.world())
+        .map(|dimensions| dimensions.length)
+        .collect();
+    assert_eq!(leaf_lengths, vec![1.0, 1.0]);
+
+    let mut joint_query = app.world_mut().query_filtered::<(), With<DoorJoint>>();
+    assert_eq!(joint_query.iter(app.world()).count(), 2);
+    let registry = app.world().resource::<DoorRegistry>();
+    assert_eq!(registry.doors("test_door"), &[door]);
+    assert_eq!(registry.get("test_door").len(), 2);
+}
+
+#[test]
+fn resized_roll_up_door_rises_by_its_new_height() {
+    let mut app = door_app();
+    let door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new("test_door".to_string(), 5.0, DoorType::RollUp)
+                .with_speed(3.0),
+            door_dimensions: DoorDimensions::new(2.0, 2.0, 0.05),
+            ..Default::default()
+        })
+        .id();
+    step(&mut app, 3);
+
+    app.world_mut()
+        .get_mut::<DoorDimensions>(door)
+        .unwrap()
+        .height = 3.0;
+    step(&mut app, 4);
+
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<(&DoorState, &Transform), With<DoorJoint>>();
+    let closed = joint_query.single(app.world()).1.translation.y;
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 80);
+
+    let (state, transform) = joint_query.single(app.world());
+    assert_eq!(*state, DoorState::Open);
+    assert!((transform.translation.y - closed - 3.0).abs() < 1e-4);
+}
+
+#[test]
+fn airlock_refuses_opening_both_doors_in_the_same_frame() {
+    let mut app = door_app();
+    let mut spawn_door = |name: &str| {
+        app.world_mut()
+            .spawn(DoorBundle {
+                door_properties: DoorProperties::new(
+                    name.to_string(),
+                    1.5,
+                    DoorType::SingleSwinging,
+                ),
+                door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+                ..Default::default()
+            })
+            .id()
+    };
+    let door_a = spawn_door("door_a");
+    let door_b = spawn_door("door_b");
+    app.world_mut()
+        .spawn(AirlockPair::new("door_a".to_string(), "door_b".to_string()));
+    step(&mut app, 2);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("door_a".to_string()));
+    app.world_mut()
+        .send_event(DoorEvent::open("door_b".to_string()));
+    step(&mut app, 1);
+
+    let registry = app.world().resource::<DoorRegistry>();
+    let (joint_a, joint_b) = (registry.get("door_a")[0], registry.get("door_b")[0]);
+    assert_eq!(registry.doors("door_a"), &[door_a]);
+    assert_eq!(registry.doors("door_b"), &[door_b]);
+    assert_eq!(
+        *app.world().get::<DoorGoal>(joint_a).unwrap(),
+        DoorGoal::Open
+    );
+    assert_eq!(
+        *app.world().get::<DoorGoal>(joint_b).unwrap(),
+        DoorGoal::Closed
+    );
+}
+
+#[test]
+fn infrastructure_plugin_runs_with_lifts_only() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.init_resource::<Assets<Mesh>>();
+    app.init_resource::<Assets<StandardMaterial>>();
+    app.add_plugins(
+        crate::BevyInfrastructurePlugin::default().with_lifts(LiftPluginConfig::default()),
+    );
+    step(&mut app, 3);
+
+    assert!(app.world().get_resource::<DoorRegistry>().is_none());
+}
+
+/// Get the state changes sent since the last call, as old and new states.
+fn drain_state_changes(app: &mut App) -> Vec<(DoorState, DoorState)> {
+    app.world_mut()
+        .resource_mut::<Events<DoorStateChanged>>()
+        .drain()
+        .map(|state_change| (state_change.old_state, state_change.new_state))
+        .collect()
+}
+
+#[test]
+fn locking_a_moving_door_snaps_it_closed() {
+    let mut app = door_app();
+    spawn_test_door(&mut app, 1.5);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 5);
+    drain_state_changes(&mut app);
+
+    app.world_mut()
+        .send_event(DoorLockEvent::lock("test_door".to_string()));
+    step(&mut app, 1);
+    assert_eq!(
+        drain_state_changes(&mut app),
+        vec![(DoorState::Opening, DoorState::Closed)]
+    );
+    assert_eq!(joint(&mut app), (DoorState::Closed, 0.0));
+
+    let mut progress_query = app
+        .world_mut()
+        .query::<(&DoorOpenProgress, &DoorAnimationProgress)>();
+    let (progress, animation) = progress_query.single(app.world());
+    assert_eq!(progress.open_fraction(), 0.0);
+    assert_eq!(animation.progress(), 0.0);
+}
+
+#[test]
+fn recovering_a_stuck_door_sends_its_state_change() {
+    let mut app = door_app();
+    let door = spawn_test_door(&mut app, 1.5);
+    app.world_mut()
+        .entity_mut(door)
+        .insert(DoorStuckTimeout::new(0.05));
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    let mut state_changes = Vec::new();
+    for _ in 0..6 {
+        step(&mut app, 1);
+        state_changes.extend(drain_state_changes(&mut app));
+    }
+
+    assert_eq!(
+        state_changes,
+        vec![
+            (DoorState::Closed, DoorState::Opening),
+            (DoorState::Opening, DoorState::Closed)
+        ]
+    );
+    assert_eq!(joint(&mut app), (DoorState::Closed, 0.0));
+}
+
+#[test]
+fn cloned_door_copies_the_reflected_components() {
+    let mut app = door_app();
+    let source = spawn_test_door(&mut app, 1.5);
+    app.world_mut().entity_mut(source).insert((
+        DoorDimensions::new(2.0, 2.5, 0.1),
+        DoorGroup("lobby".to_string()),
+        DoorAutoClose::new(3.0),
+        DoorEventLog::new(3),
+    ));
+    step(&mut app, 2);
+
+    app.world_mut().send_event(DoorCloneEvent::new(
+        "test_door".to_string(),
+        "cloned_door".to_string(),
+        Transform::from_xyz(5.0, 0.0, 0.0),
+    ));
+    step(&mut app, 2);
+
+    let clone = app.world().resource::<DoorRegistry>().doors("cloned_door")[0];
+    let clone = app.world().entity(clone);
+    assert_eq!(clone.get::<DoorProperties>().unwrap().name, "cloned_door");
+    assert_eq!(clone.get::<Transform>().unwrap().translation.x, 5.0);
+    assert!(clone.get::<DoorDimensions>().unwrap() == &DoorDimensions::new(2.0, 2.5, 0.1));
+    assert_eq!(clone.get::<DoorGroup>().unwrap().0, "lobby");
+    assert_eq!(clone.get::<DoorAutoClose>().unwrap().delay_seconds, 3.0);
+    let event_log = clone.get::<DoorEventLog>().unwrap();
+    assert_eq!(event_log.capacity(), 3);
+    assert!(event_log.entries.is_empty());
+}
+
+#[test]
+fn cloned_door_copies_the_components_that_are_not_reflected() {
+    let mut app = door_app();
+    let source = app
+        .world_mut()
+        .spawn((
+            DoorBundle {
+                door_properties: DoorProperties::new(
+                    "test_door".to_string(),
+                    1.5,
+                    DoorType::SingleSwinging,
+                ),
+                door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+                ..Default::default()
+            },
+            DoorSchedule::new(8.0, 18.0),
+            DoorGlass {
+                alpha: 0.5,
+                ..Default::default()
+            },
+        ))
+        .id();
+    step(&mut app, 2);
+    assert!(app.world().get::<DoorSchedule>(source).is_some());
+
+    app.world_mut().send_event(DoorCloneEvent::new(
+        "test_door".to_string(),
+        "cloned_door".to_string(),
+        Transform::from_xyz(5.0, 0.0, 0.0),
+    ));
+    step(&mut app, 2);
+
+    let clone = app.world().resource::<DoorRegistry>().doors("cloned_door")[0];
+    let schedule = app.world().get::<DoorSchedule>(clone).unwrap();
+    assert_eq!((schedule.unlock_hour, schedule.lock_hour), (8.0, 18.0));
+    assert_eq!(app.world().get::<DoorGlass>(clone).unwrap().alpha, 0.5);
+}
+
+#[test]
+fn centre_hung_door_is_blocked_behind_its_pivot() {
+    use bevy::render::primitives::Aabb;
+
+    let mut app = door_app();
+    let door = app
+        .world_mut()
+        .spawn((
+            DoorBundle {
+                door_properties: DoorProperties::new(
+                    "test_door".to_string(),
+                    1.5,
+                    DoorType::SingleSwinging,
+                )
+                .with_anchor_offset(Vec3::new(0.5, 0.0, 0.0)),
+                door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+                ..Default::default()
+            },
+            DoorClearanceBox::default(),
+        ))
+        .id();
+    // In the way of the half of the panel behind the pivot, clear of the other half
+    app.world_mut().spawn((
+        Obstacle,
+        Aabb::from_min_max(Vec3::splat(-0.05), Vec3::splat(0.05)),
+        GlobalTransform::from_xyz(0.2, 1.0, 0.3),
+    ));
+    step(&mut app, 2);
+
+    let clearances = &app
+        .world()
+        .get::<DoorClearanceBox>(door)
+        .unwrap()
+        .clearances;
+    let radii: Vec<f32> = clearances
+        .iter()
+        .filter_map(|clearance| match clearance {
+            DoorClearance::Swept(volume) => Some(volume.radius),
+            DoorClearance::Box(_) => None,
+        })
+        .collect();
+    assert_eq!(radii, vec![0.5, 0.5]);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 150);
+    let (state, angle) = joint(&mut app);
+    assert_eq!(state, DoorState::Blocked);
+    assert!(angle > 0.0 && angle < std::f32::consts::FRAC_PI_4);
+}
+
+#[test]
+fn lift_config_hold_time_applies_to_lifts_without_one() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.init_resource::<Assets<Mesh>>();
+    app.init_resource::<Assets<StandardMaterial>>();
+    app.add_plugins(
+        crate::BevyInfrastructurePlugin::default().with_lifts(LiftPluginConfig {
+            default_door_hold_seconds: 10.0,
+        }),
+    );
+
+    let mut spawn_lift = |properties: LiftProperties| {
+        app.world_mut()
+            .spawn(LiftBundle {
+                lift_properties: properties,
+                lift_dimensions: LiftDimensions::new(1.0, 1.0, 0.1),
+                ..Default::default()
+            })
+            .id()
+    };
+    let lift = spawn_lift(LiftProperties::new("lift".to_string(), vec![0.0, 3.0], 1.0));
+    let explicit_lift = spawn_lift(
+        LiftProperties::new("explicit_lift".to_string(), vec![0.0, 3.0], 1.0)
+            .with_door_hold_seconds(DEFAULT_LIFT_DOOR_HOLD_SECONDS),
+    );
+    step(&mut app, 2);
+
+    let hold_seconds = |lift| {
+        app.world()
+            .get::<LiftProperties>(lift)
+            .unwrap()
+            .door_hold_seconds()
+    };
+    assert_eq!(hold_seconds(lift), 10.0);
+    assert_eq!(hold_seconds(explicit_lift), DEFAULT_LIFT_DOOR_HOLD_SECONDS);
+}
+
+#[test]
+fn door_builder_makes_the_snap_tolerance_positive() {
+    let bundle = DoorBuilder::new().snap_tolerance(-0.1).build();
+    assert_eq!(bundle.door_properties.snap_tolerance(), 0.1);
+}
+
+#[test]
+fn door_builder_sets_the_same_properties_as_the_properties_builder() {
+    let bundle = DoorBuilder::new()
+        .name("built_door".to_string())
+        .door_type(DoorType::SingleSwinging)
+        .swing_value(1.2)
+        .speed(0.9)
+        .anchor_offset(Vec3::new(0.5, 0.0, 0.0))
+        .easing(DoorAnimationCurve::EaseOut)
+        .build();
+    let properties = DoorPropertiesBuilder::new()
+        .name("built_door".to_string())
+        .door_type(DoorType::SingleSwinging)
+        .swing_value(1.2)
+        .speed(0.9)
+        .anchor_offset(Vec3::new(0.5, 0.0, 0.0))
+        .easing(DoorAnimationCurve::EaseOut)
+        .build()
+        .unwrap();
+
+    let built = &bundle.door_properties;
+    assert_eq!(built.name(), properties.name());
+    assert_eq!(built.speed, Some(0.9));
+    assert_eq!(built.speed, properties.speed);
+    assert_eq!(built.anchor_offset(), properties.anchor_offset());
+    assert_eq!(built.easing(), DoorAnimationCurve::EaseOut);
+}
+
+#[test]
+fn locks_resolve_groups_and_every_door() {
+    let mut app = door_app();
+    let door = spawn_test_door(&mut app, 1.5);
+    app.world_mut()
+        .entity_mut(door)
+        .insert(DoorGroup("lobby".to_string()));
+    step(&mut app, 1);
+    let locked = |app: &App| app.world().get::<DoorLock>(door).unwrap().locked;
+
+    app.world_mut()
+        .send_event(DoorEvent::lock_group("lobby".to_string()));
+    step(&mut app, 1);
+    assert!(locked(&app));
+
+    app.world_mut().send_event(DoorEvent::unlock_all());
+    step(&mut app, 1);
+    assert!(!locked(&app));
+
+    app.world_mut().send_event(DoorEvent::lock_all());
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 10);
+    assert!(locked(&app));
+    assert_eq!(joint(&mut app), (DoorState::Closed, 0.0));
+}
+
+#[test]
+fn lock_events_resolve_a_leaf_suffix() {
+    let mut app = door_app();
+    app.world_mut().spawn(DoorBundle {
+        door_properties: DoorProperties::new(
+            "double_door".to_string(),
+            1.5,
+            DoorType::DoubleSwinging,
+        ),
+        door_dimensions: DoorDimensions::new(2.0, 2.0, 0.05),
+        ..Default::default()
+    });
+    step(&mut app, 3);
+
+    app.world_mut().send_event(DoorLockEvent::lock(format!(
+        "double_door{}left",
+        DOOR_LEAF_SEPARATOR
+    )));
+    step(&mut app, 1);
+
+    let mut leaf_query = app
+        .world_mut()
+        .query_filtered::<(&DoorLeafSide, &DoorLock), With<DoorLeaf>>();
+    let mut leaf_locks: Vec<(bool, bool)> = leaf_query
+        .iter(app.world())
+        .map(|(side, lock)| (*side == DoorLeafSide::Left, lock.locked))
+        .collect();
+    leaf_locks.sort();
+    assert_eq!(leaf_locks, vec![(false, false), (true, true)]);
+}
+
+#[test]
+fn double_action_door_opens_to_either_side() {
+    let mut app = door_app();
+    app.world_mut().spawn(DoorBundle {
+        door_properties: DoorProperties::new("test_door".to_string(), 1.5, DoorType::DoubleAction)
+            .with_speed(2.0),
+        door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+        ..Default::default()
+    });
+    step(&mut app, 2);
+
+    app.world_mut().send_event(DoorEvent::open_from(
+        "test_door".to_string(),
+        DoorSide::Front,
+    ));
+    step(&mut app, 60);
+    let (state, angle) = joint(&mut app);
+    assert_eq!(state, DoorState::Open);
+    assert!((angle - 1.5).abs() < 1e-4);
+
+    app.world_mut()
+        .send_event(DoorEvent::close("test_door".to_string()));
+    step(&mut app, 60);
+    assert_eq!(joint(&mut app), (DoorState::Closed, 0.0));
+
+    app.world_mut().send_event(DoorEvent::open_from(
+        "test_door".to_string(),
+        DoorSide::Back,
+    ));
+    step(&mut app, 60);
+    let (state, angle) = joint(&mut app);
+    assert_eq!(state, DoorState::Open);
+    assert!((angle + 1.5).abs() < 1e-4);
+}
+
+#[test]
+fn door_event_log_drops_the_oldest_entries_at_capacity() {
+    let mut event_log = DoorEventLog::new(2);
+    for seconds in 1..=3 {
+        event_log.push(
+            Duration::from_secs(seconds),
+            DoorEventKind::StateChanged(DoorState::Open),
+        );
+    }
+
+    let times: Vec<Duration> = event_log.entries.iter().map(|(time, _)| *time).collect();
+    assert_eq!(times, vec![Duration::from_secs(2), Duration::from_secs(3)]);
+    assert_eq!(event_log.capacity(), 2);
+
+    // Doors append their commands and state changes as they happen
+    let mut app = door_app();
+    let door = spawn_test_door(&mut app, 1.5);
+    app.world_mut()
+        .entity_mut(door)
+        .insert(DoorEventLog::new(2));
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 60);
+
+    let event_log = app.world().get::<DoorEventLog>(door).unwrap();
+    let kinds: Vec<DoorEventKind> = event_log.entries.iter().map(|(_, kind)| *kind).collect();
+    assert!(
+        kinds
+            == vec![
+                DoorEventKind::StateChanged(DoorState::Opening),
+                DoorEventKind::StateChanged(DoorState::Open),
+            ]
+    );
+}
+
+#[test]
+fn glass_doors_get_a_transparent_transmissive_material() {
+    let glass = DoorGlass {
+        alpha: 0.4,
+        transmission: 0.8,
+        ..Default::default()
+    };
+    let material = glass.material(0.05);
+    assert_eq!(material.base_color.alpha(), 0.4);
+    assert_eq!(material.specular_transmission, 0.8);
+    assert_eq!(material.thickness, 0.05);
+    assert!(matches!(material.alpha_mode, AlphaMode::Blend));
+
+    let mut app = door_app();
+    app.world_mut().spawn((
+        DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                1.5,
+                DoorType::SingleSwinging,
+            ),
+            door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+            ..Default::default()
+        },
+        glass,
+    ));
+    step(&mut app, 2);
+
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<&Children, With<DoorJoint>>();
+    let panel = joint_query.single(app.world())[0];
+    let handle = app
+        .world()
+        .get::<Handle<StandardMaterial>>(panel)
+        .unwrap()
+        .clone();
+    let materials = app.world().resource::<Assets<StandardMaterial>>();
+    let panel_material = materials.get(&handle).unwrap();
+    assert_eq!(panel_material.base_color.alpha(), 0.4);
+    assert_eq!(panel_material.specular_transmission, 0.8);
+}
+
+#[test]
+fn non_finite_door_values_are_replaced_with_defaults() {
+    let mut app = door_app();
+    let door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                f32::NAN,
+                DoorType::SingleSwinging,
+            )
+            .with_speed(f32::INFINITY)
+            .with_snap_tolerance(f32::NAN),
+            door_dimensions: DoorDimensions::new(f32::NAN, f32::INFINITY, f32::NEG_INFINITY),
+            ..Default::default()
+        })
+        .id();
+    step(&mut app, 2);
+
+    let properties = app.world().get::<DoorProperties>(door).unwrap();
+    assert_eq!(
+        properties.swing_value(),
+        DoorProperties::default().swing_value()
+    );
+    assert_eq!(properties.speed(), DEFAULT_DOOR_SPEED);
+    assert_eq!(properties.snap_tolerance(), DEFAULT_DOOR_SNAP_TOLERANCE);
+
+    // The default dimensions are then clamped like any other
+    let dimensions = app.world().get::<DoorDimensions>(door).unwrap();
+    let default = DoorDimensions::default();
+    assert_eq!(dimensions.length, default.length.max(MIN_DOOR_DIMENSION));
+    assert_eq!(dimensions.height, default.height.max(MIN_DOOR_DIMENSION));
+    assert_eq!(
+        dimensions.thickness,
+        default.thickness.max(MIN_DOOR_DIMENSION)
+    );
+    assert_eq!(joint(&mut app), (DoorState::Closed, 0.0));
+}
+
+#[test]
+fn bi_fold_doors_spawn_a_joint_and_two_panels_per_leaf() {
+    for (panels, joints) in [(2, 1), (4, 2)] {
+        let mut app = door_app();
+        app.world_mut().spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                1.5,
+                DoorType::BiFold { panels },
+            ),
+            door_dimensions: DoorDimensions::new(2.0, 2.0, 0.05),
+            ..Default::default()
+        });
+        step(&mut app, 3);
+
+        let mut joint_query = app.world_mut().query_filtered::<(), With<DoorJoint>>();
+        assert_eq!(joint_query.iter(app.world()).count(), joints);
+        let mut fold_query = app.world_mut().query_filtered::<(), With<BiFoldJoint>>();
+        assert_eq!(fold_query.iter(app.world()).count(), joints);
+        // The joints are spawned as meshless PBR bundles, so only the panels are left
+        let mut panel_query = app.world_mut().query_filtered::<(), (
+            With<Handle<Mesh>>,
+            Without<DoorJoint>,
+            Without<BiFoldJoint>,
+            Without<DoorFrame>,
+        )>();
+        assert_eq!(panel_query.iter(app.world()).count(), panels as usize);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn door_properties_round_trip_through_ron() {
+    let properties = DoorProperties::new("ron_door".to_string(), -1.2, DoorType::DoubleSliding)
+        .with_speed(0.8)
+        .with_hinge_side(HingeSide::Right)
+        .with_anchor_offset(Vec3::new(0.1, 0.0, 0.05));
+
+    let serialized = ron::to_string(&properties).unwrap();
+    assert!(serialized.contains("double_sliding"));
+    let deserialized: DoorProperties = ron::from_str(&serialized).unwrap();
+
+    assert_eq!(deserialized.name(), "ron_door");
+    assert_eq!(deserialized.swing_value(), -1.2);
+    assert_eq!(deserialized.door_type(), &DoorType::DoubleSliding);
+    assert_eq!(deserialized.speed, Some(0.8));
+    assert!(deserialized.hinge_side() == HingeSide::Right);
+    assert_eq!(deserialized.anchor_offset(), Vec3::new(0.1, 0.0, 0.05));
+    assert_eq!(deserialized.snap_tolerance(), properties.snap_tolerance());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn door_config_round_trips_through_json() {
+    let config = DoorConfig {
+        name: "json_door".to_string(),
+        swing_value: 1.4,
+        door_type: DoorType::DoubleSwinging,
+        speed: None,
+        anchor_offset: [0.05, 0.0, -0.02],
+        length: 1.8,
+        height: 2.1,
+        thickness: 0.05,
+        locked: true,
+        lock_reason: "maintenance".to_string(),
+        translation: [1.0, 0.0, -2.0],
+        ..Default::default()
+    };
+
+    let serialized = serde_json::to_string(&config).unwrap();
+    let deserialized: DoorConfig = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, config);
+    assert_eq!(deserialized.speed, None);
+    assert_eq!(deserialized.anchor_offset, [0.05, 0.0, -0.02]);
+}
+
+#[test]
+fn swing_direction_does_not_move_the_closed_door() {
+    let mut closed = Vec::new();
+    let mut opened = Vec::new();
+    for direction in [SwingDirection::Inward, SwingDirection::Outward] {
+        let mut app = door_app();
+        app.world_mut().spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                1.5,
+                DoorType::SingleSwinging,
+            )
+            .with_speed(2.0)
+            .with_swing_direction(direction),
+            door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+            ..Default::default()
+        });
+        step(&mut app, 2);
+
+        let mut joint_query = app
+            .world_mut()
+            .query_filtered::<&Transform, With<DoorJoint>>();
+        let spawned = *joint_query.single(app.world());
+
+        app.world_mut()
+            .send_event(DoorEvent::open("test_door".to_string()));
+        step(&mut app, 60);
+        opened.push(joint(&mut app).1);
+        app.world_mut()
+            .send_event(DoorEvent::close("test_door".to_string()));
+        step(&mut app, 60);
+
+        assert_eq!(joint(&mut app).0, DoorState::Closed);
+        assert_eq!(*joint_query.single(app.world()), spawned);
+        closed.push(spawned);
+    }
+
+    assert_eq!(closed[0], closed[1]);
+    assert!(
+        opened[0] * opened[1] < 0.0,
+        "both swings opened to {:?}",
+        opened
+    );
+}
+
+#[test]
+fn closing_a_partly_opened_door_reverses_to_closed() {
+    let mut app = door_app();
+    spawn_test_door(&mut app, 1.5);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 20);
+    let (state, partial) = joint(&mut app);
+    assert_eq!(state, DoorState::Opening);
+    assert!(partial > 0.1 && partial < 1.4, "door opened to {}", partial);
+
+    app.world_mut()
+        .send_event(DoorEvent::close("test_door".to_string()));
+    step(&mut app, 2);
+    let (state, reversed) = joint(&mut app);
+    assert_eq!(state, DoorState::Closing);
+    assert!(reversed < partial + 0.1, "door jumped to {}", reversed);
+
+    step(&mut app, 60);
+    let (state, angle) = joint(&mut app);
+    assert_eq!(state, DoorState::Closed);
+    assert!(angle.abs() < 1e-4, "door stopped at {}", angle);
+    let mut progress_query = app
+        .world_mut()
+        .query_filtered::<&DoorAnimationProgress, With<DoorJoint>>();
+    assert_eq!(progress_query.single(app.world()).0, 0.0);
+}
+
+#[test]
+fn double_sliding_door_spawns_its_leaves_under_the_door() {
+    let mut app = door_app();
+    let door = app
+        .world_mut()
+        .spawn(DoorBundle {
+            door_properties: DoorProperties::new(
+                "test_door".to_string(),
+                0.5,
+                DoorType::DoubleSliding,
+            ),
+            door_dimensions: DoorDimensions::new(2.0, 2.0, 0.05),
+            ..Default::default()
+        })
+        .id();
+    step(&mut app, 2);
+    let spawned = app.world().entities().len();
+
+    // Spawning again must not expand the leaves a second time
+    step(&mut app, 4);
+    assert_eq!(app.world().entities().len(), spawned);
+
+    let mut leaf_query = app
+        .world_mut()
+        .query_filtered::<&Parent, (With<DoorLeaf>, With<DoorProperties>)>();
+    let leaves: Vec<Entity> = leaf_query
+        .iter(app.world())
+        .map(|parent| parent.get())
+        .collect();
+    assert_eq!(leaves, vec![door, door]);
+
+    let mut joint_query = app.world_mut().query_filtered::<(), With<DoorJoint>>();
+    assert_eq!(joint_query.iter(app.world()).count(), 2);
+    let mut door_query = app
+        .world_mut()
+        .query_filtered::<(), (With<DoorProperties>, Without<Parent>)>();
+    assert_eq!(door_query.iter(app.world()).count(), 1);
+}
+
+#[test]
+fn proximity_triggers_react_to_any_entity_without_a_target_tag() {
+    for (target_tag, tag, opens) in [
+        (None, None, true),
+        (Some("robot"), None, false),
+        (Some("robot"), Some("robot"), true),
+    ] {
+        let mut trigger = ProximityDoorTrigger::new(1.0);
+        if let Some(target_tag) = target_tag {
+            trigger = trigger.with_target_tag(target_tag.to_string());
+        }
+        let mut app = door_app();
+        app.world_mut().spawn((
+            DoorBundle {
+                door_properties: DoorProperties::new(
+                    "test_door".to_string(),
+                    1.5,
+                    DoorType::SingleSwinging,
+                ),
+                door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+                ..Default::default()
+            },
+            trigger,
+        ));
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(0.5, 0.0, 0.0))
+            .id();
+        if let Some(tag) = tag {
+            app.world_mut()
+                .entity_mut(target)
+                .insert(DoorTriggerTag(tag.to_string()));
+        }
+        step(&mut app, 4);
+
+        let opening = joint(&mut app).0 == DoorState::Opening;
+        assert_eq!(opening, opens, "target tag {:?} tag {:?}", target_tag, tag);
+    }
+}
+
+#[test]
+fn infrastructure_plugin_passes_the_door_plugin_through() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.init_resource::<Assets<Mesh>>();
+    app.init_resource::<Assets<StandardMaterial>>();
+    app.add_plugins(
+        crate::BevyInfrastructurePlugin::default()
+            .with_door_plugin(BevyDoorPlugin { strict_mode: true }),
+    );
+    step(&mut app, 2);
+
+    assert!(app.world().resource::<DoorStrictMode>().0);
+    assert!(app.world().get_resource::<DoorRegistry>().is_some());
+    assert!(app.world().get_resource::<LiftPluginConfig>().is_none());
+}
+
+#[test]
+fn fast_doors_snap_within_two_frames_of_travel() {
+    let mut app = door_app();
+    app.world_mut().spawn(DoorBundle {
+        door_properties: DoorProperties::new("test_door".to_string(), 1.0, DoorType::SingleSliding)
+            .with_speed(10.0),
+        door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+        ..Default::default()
+    });
+    step(&mut app, 2);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    let snap_distance = 10.0 * 0.016 * 2.0;
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<(&DoorState, &DoorOpenProgress), With<DoorJoint>>();
+    for _ in 0..20 {
+        app.update();
+        let (state, progress) = joint_query.single(app.world());
+        if *state == DoorState::Opening {
+            assert!(1.0 - progress.open_fraction() > snap_distance - 1e-4);
+        }
+    }
+    assert_eq!(*joint_query.single(app.world()).0, DoorState::Open);
+}
+
+#[test]
+fn auto_close_only_closes_its_own_door() {
+    let mut app = door_app();
+    for auto_close in [true, false] {
+        let door = app
+            .world_mut()
+            .spawn(DoorBundle {
+                door_properties: DoorProperties::new(
+                    "twin_door".to_string(),
+                    1.5,
+                    DoorType::SingleSwinging,
+                )
+                .with_speed(2.0),
+                door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+                ..Default::default()
+            })
+            .id();
+        if auto_close {
+            app.world_mut()
+                .entity_mut(door)
+                .insert(DoorAutoClose::new(0.5));
+        }
+    }
+    step(&mut app, 2);
+
+    app.world_mut()
+        .send_event(DoorEvent::open("twin_door".to_string()));
+    step(&mut app, 160);
+
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<(&Parent, &DoorState), With<DoorJoint>>();
+    let joints: Vec<(Entity, DoorState)> = joint_query
+        .iter(app.world())
+        .map(|(parent, state)| (parent.get(), *state))
+        .collect();
+    for (door, state) in joints {
+        let auto_close = app.world().get::<DoorAutoClose>(door).is_some();
+        let expected = if auto_close {
+            DoorState::Closed
+        } else {
+            DoorState::Open
+        };
+        assert_eq!(state, expected);
+    }
+}
+
+#[test]
+fn opening_a_locked_door_leaves_the_auto_close_timer_running() {
+    let mut app = door_app();
+    let door = spawn_test_door(&mut app, 1.5);
+    app.world_mut()
+        .entity_mut(door)
+        .insert(DoorAutoClose::new(1.0));
+
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 60);
+    assert_eq!(joint(&mut app).0, DoorState::Open);
+    app.world_mut()
+        .send_event(DoorEvent::lock("test_door".to_string()));
+    step(&mut app, 20);
+
+    let elapsed = app.world().get::<DoorAutoClose>(door).unwrap().elapsed;
+    assert!(elapsed > 0.0);
+    app.world_mut()
+        .send_event(DoorEvent::open("test_door".to_string()));
+    step(&mut app, 1);
+    assert!(app.world().get::<DoorAutoClose>(door).unwrap().elapsed > elapsed);
+}
+
+#[test]
+fn door_properties_easing_shapes_the_movement() {
+    let mut app = door_app();
+    for (name, easing) in [
+        ("linear_door", DoorAnimationCurve::Linear),
+        ("eased_door", DoorAnimationCurve::EaseInOut),
+    ] {
+        app.world_mut().spawn(DoorBundle {
+            door_properties: DoorProperties::new(name.to_string(), 1.5, DoorType::SingleSwinging)
+                .with_speed(2.0)
+                .with_easing(easing),
+            door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+            ..Default::default()
+        });
+    }
+    step(&mut app, 2);
+
+    app.world_mut().send_event(DoorEvent::open_all());
+    step(&mut app, 10);
+
+    let mut joint_query = app
+        .world_mut()
+        .query_filtered::<(&Parent, &DoorOpenProgress), With<DoorJoint>>();
+    let mut progress = std::collections::HashMap::new();
+    for (parent, open_progress) in joint_query.iter(app.world()) {
+        let name = app
+            .world()
+            .get::<DoorProperties>(parent.get())
+            .unwrap()
+            .name();
+        progress.insert(name.to_string(), open_progress.open_fraction());
+    }
+    assert!(progress["eased_door"] > 0.0);
+    assert!(progress["eased_door"] < progress["linear_door"]);
+}
+
+#[test]
+fn door_sensors_ignore_the_scenery_around_them() {
+    let mut app = door_app();
+    let door = app
+        .world_mut()
+        .spawn((
+            DoorBundle {
+                door_properties: DoorProperties::new(
+                    "test_door".to_string(),
+                    1.5,
+                    DoorType::SingleSwinging,
+                ),
+                door_dimensions: DoorDimensions::new(1.0, 2.0, 0.05),
+                ..Default::default()
+            },
+            DoorSensor::new(DoorSensorType::MotionDetector, 2.0),
+        ))
+        .id();
+    let wall = app
+        .world_mut()
+        .spawn((
+            WallProperties::new("wall".to_string(), Vec::new()),
+            GlobalTransform::from_xyz(0.5, 0.0, 0.0),
+        ))
+        .id();
+    app.world_mut()
+        .spawn(GlobalTransform::from_xyz(0.2, 0.0, 0.0))
+        .set_parent(wall);
+    let mut visitor = None;
+    let mut triggers = Vec::new();
+    for frame in 0..6 {
+        if frame == 3 {
+            visitor = Some(
+                app.world_mut()
+                    .spawn(GlobalTransform::from_xyz(1.0, 0.0, 0.0))
+                    .id(),
+            );
+        }
+        step(&mut app, 1);
+        let mut trigger_events = app
+            .world_mut()
+            .resource_mut::<Events<DoorSensorTriggered>>();
+        triggers.extend(
+            trigger_events
+                .drain()
+                .map(|trigger| (trigger.door_entity, trigger.triggered_by)),
+        );
+    }
+    assert_eq!(triggers, vec![(door, visitor.unwrap())]);
+}
+
+#[test]
+fn despawning_a_name_removes_every_door_with_it() {
+    let mut app = door_app();
+    for _ in 0..2 {
+        spawn_test_door(&mut app, 1.5);
+    }
+    assert_eq!(
+        app.world()
+            .resource::<DoorRegistry>()
+            .doors("test_door")
+            .len(),
+        2
+    );
+
+    app.world_mut()
+        .send_event(DespawnDoorEvent::new("test_door".to_string()));
+    step(&mut app, 2);
+
+    let mut door_query = app.world_mut().query::<&DoorProperties>();
+    assert_eq!(door_query.iter(app.world()).count(), 0);
+    let mut joint_query = app.world_mut().query_filtered::<(), With<DoorJoint>>();
+    assert_eq!(joint_query.iter(app.world()).count(), 0);
+    assert!(app.world().resource::<DoorRegistry>().is_empty());
+}
@@ -0,0 +1,99 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The default color of opaque door panels.
+pub const DEFAULT_DOOR_COLOR: Color = Color::srgb(124.0 / 255.0, 144.0 / 255.0, 1.0);
+
+/// A resource to store the look shared by all opaque door panels.
+///
+/// Send a `DoorThemeChanged` event after changing it to update doors that are already spawned.
+#[derive(Resource, Clone)]
+pub struct DoorTheme {
+    pub primary_color: Color,
+    pub emissive: Color,
+    pub metallic: f32,
+    pub roughness: f32,
+}
+
+impl Default for DoorTheme {
+    fn default() -> Self {
+        DoorTheme {
+            primary_color: DEFAULT_DOOR_COLOR,
+            emissive: Color::BLACK,
+            metallic: 0.0,
+            roughness: 0.5,
+        }
+    }
+}
+
+impl DoorTheme {
+    /// Create the material for an opaque door panel.
+    pub fn material(&self) -> StandardMaterial {
+        let mut material = StandardMaterial::default();
+        self.apply(&mut material);
+        material
+    }
+
+    /// Update a door panel material to match the theme.
+    pub fn apply(&self, material: &mut StandardMaterial) {
+        material.base_color = self.primary_color;
+        material.emissive = self.emissive.to_linear();
+        material.metallic = self.metallic;
+        material.perceptual_roughness = self.roughness;
+    }
+}
+
+/// A Bevy event to apply the current `DoorTheme` to every spawned door.
+#[derive(Event)]
+pub struct DoorThemeChanged;
+
+/// A system to apply the door theme to the door panels.
+///
+/// Panels of doors with a `DoorGlass` or `DoorMaterial` component, or with a material in their
+/// `DoorMeshOverride`, keep their own material.
+pub(crate) fn apply_door_theme(
+    theme: Res<DoorTheme>,
+    mut theme_changes: EventReader<DoorThemeChanged>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    glass_queries: Query<(), Or<(With<DoorGlass>, With<DoorMaterial>)>>,
+    mesh_override_queries: Query<&DoorMeshOverride>,
+    children_queries: Query<&Children>,
+    joint_queries: Query<(Entity, &Parent), With<DoorJoint>>,
+    panel_queries: Query<&Handle<StandardMaterial>, Without<DoorJoint>>,
+) {
+    if theme_changes.is_empty() {
+        return;
+    }
+    theme_changes.clear();
+
+    for (joint, door_entity) in joint_queries.iter() {
+        let has_material = mesh_override_queries
+            .get(door_entity.get())
+            .is_ok_and(|mesh_override| mesh_override.material.is_some());
+        if has_material || glass_queries.contains(door_entity.get()) {
+            continue;
+        }
+        for material in panel_queries.iter_many(children_queries.iter_descendants(joint)) {
+            if let Some(material) = materials.get_mut(material) {
+                theme.apply(material);
+            }
+        }
+    }
+}
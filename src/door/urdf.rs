@@ -0,0 +1,155 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+use std::fmt::Write;
+
+/// The effort limit given to exported door joints, in newtons or newton metres.
+pub const URDF_DOOR_EFFORT: f32 = 100.0;
+
+/// Export every door in the world as URDF links and joints.
+///
+/// Single sliding and pocket doors become prismatic joints along the door, and single swinging
+/// and double action doors revolute joints about their hinge, leaves of composite doors included.
+/// Each joint connects a `world` link to a box link the size of the panel, with its limits taken
+/// from the swing value and its velocity from the door speed. Other door types are skipped.
+///
+/// Bevy is y up and URDF z up, so positions and axes are turned from Bevy's `(x, y, z)` into
+/// URDF's `(x, -z, y)`.
+pub fn export_doors_to_urdf(world: &World) -> String {
+    let mut urdf = String::new();
+    writeln!(urdf, r#"<?xml version="1.0"?>"#).unwrap();
+    writeln!(urdf, r#"<robot name="doors">"#).unwrap();
+    writeln!(urdf, r#"  <link name="world"/>"#).unwrap();
+
+    for entity in world.iter_entities() {
+        let (Some(properties), Some(dimensions)) = (
+            entity.get::<DoorProperties>(),
+            entity.get::<DoorDimensions>(),
+        ) else {
+            continue;
+        };
+
+        let (joint_type, axis) = match properties.door_type {
+            DoorType::SingleSliding | DoorType::PocketDoor { .. } => ("prismatic", Vec3::X),
+            DoorType::SingleSwinging | DoorType::DoubleAction => ("revolute", Vec3::Y),
+            _ => continue,
+        };
+
+        let name = match entity.get::<DoorLeafSide>() {
+            Some(side) => format!("{}_{}", properties.name, side.suffix()),
+            None => properties.name.clone(),
+        };
+
+        // The joint sits on the hinge of the closed door, and the panel is centred on the door
+        let (hinge, panel_offset) = if properties.hinged_right() {
+            (dimensions.length, -dimensions.length / 2.0)
+        } else {
+            (0.0, dimensions.length / 2.0)
+        };
+        let anchor_offset = properties.hinge_anchor_offset();
+        let door_transform = world_transform(world, entity.id());
+        let joint_position =
+            door_transform.transform_point(Vec3::new(hinge, 0.0, 0.0) + anchor_offset);
+        let panel_position = Vec3::new(panel_offset, dimensions.height / 2.0, 0.0) - anchor_offset;
+
+        let (lower, upper) = match properties.door_type {
+            DoorType::DoubleAction => (-properties.swing_value.abs(), properties.swing_value.abs()),
+            _ => {
+                let open_position = door_open_position(properties, None);
+                (open_position.min(0.0), open_position.max(0.0))
+            }
+        };
+
+        writeln!(urdf, r#"  <link name="{}_panel">"#, name).unwrap();
+        writeln!(urdf, r#"    <visual>"#).unwrap();
+        writeln!(
+            urdf,
+            r#"      <origin xyz="{}" rpy="0 0 0"/>"#,
+            urdf_vector(panel_position)
+        )
+        .unwrap();
+        writeln!(urdf, r#"      <geometry>"#).unwrap();
+        writeln!(
+            urdf,
+            r#"        <box size="{} {} {}"/>"#,
+            dimensions.length, dimensions.thickness, dimensions.height
+        )
+        .unwrap();
+        writeln!(urdf, r#"      </geometry>"#).unwrap();
+        writeln!(urdf, r#"    </visual>"#).unwrap();
+        writeln!(urdf, r#"  </link>"#).unwrap();
+
+        writeln!(
+            urdf,
+            r#"  <joint name="{}_joint" type="{}">"#,
+            name, joint_type
+        )
+        .unwrap();
+        writeln!(urdf, r#"    <parent link="world"/>"#).unwrap();
+        writeln!(urdf, r#"    <child link="{}_panel"/>"#, name).unwrap();
+        writeln!(
+            urdf,
+            r#"    <origin xyz="{}" rpy="{}"/>"#,
+            urdf_vector(joint_position),
+            urdf_rpy(door_transform.rotation)
+        )
+        .unwrap();
+        writeln!(urdf, r#"    <axis xyz="{}"/>"#, urdf_vector(axis)).unwrap();
+        writeln!(
+            urdf,
+            r#"    <limit lower="{}" upper="{}" effort="{}" velocity="{}"/>"#,
+            lower,
+            upper,
+            URDF_DOOR_EFFORT,
+            properties.speed()
+        )
+        .unwrap();
+        writeln!(urdf, r#"  </joint>"#).unwrap();
+    }
+
+    writeln!(urdf, r#"</robot>"#).unwrap();
+    urdf
+}
+
+/// Get the transform of an entity relative to the world, from its own and its parents' transforms.
+///
+/// It does not rely on `GlobalTransform`, so it also holds before transforms are propagated.
+fn world_transform(world: &World, entity: Entity) -> Transform {
+    let mut transform = world.get::<Transform>(entity).copied().unwrap_or_default();
+    let mut current = entity;
+    while let Some(parent) = world.get::<Parent>(current) {
+        if let Some(parent_transform) = world.get::<Transform>(parent.get()) {
+            transform = parent_transform.mul_transform(transform);
+        }
+        current = parent.get();
+    }
+    transform
+}
+
+/// Write a Bevy vector in URDF coordinates.
+fn urdf_vector(vector: Vec3) -> String {
+    format!("{} {} {}", vector.x, -vector.z, vector.y)
+}
+
+/// Write a Bevy rotation as URDF roll, pitch and yaw.
+fn urdf_rpy(rotation: Quat) -> String {
+    let rotation = Quat::from_xyzw(rotation.x, -rotation.z, rotation.y, rotation.w);
+    let (yaw, pitch, roll) = rotation.to_euler(EulerRot::ZYX);
+    format!("{} {} {}", roll, pitch, yaw)
+}
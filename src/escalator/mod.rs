@@ -0,0 +1,263 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The default escalator color.
+pub const DEFAULT_ESCALATOR_COLOR: Color = Color::srgb(0.4, 0.4, 0.45);
+
+/// A Bevy event to start or stop an escalator.
+#[derive(Event)]
+pub struct EscalatorEvent {
+    name: String,
+    state: EscalatorState,
+}
+
+impl EscalatorEvent {
+    pub fn start(name: String) -> Self {
+        EscalatorEvent {
+            name,
+            state: EscalatorState::Moving,
+        }
+    }
+
+    pub fn stop(name: String) -> Self {
+        EscalatorEvent {
+            name,
+            state: EscalatorState::Stopped,
+        }
+    }
+}
+
+/// A component bundle for escalators.
+#[derive(Bundle, Default)]
+pub struct EscalatorBundle {
+    pub escalator_properties: EscalatorProperties,
+    pub escalator_state: EscalatorState,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub inherited_visibility: InheritedVisibility,
+    pub view_visibility: ViewVisibility,
+}
+
+/// A component to store escalator properties.
+///
+/// Like a stair, the escalator climbs along its local X axis from its transform and spans its
+/// width along the Z axis. The speed is in units per second along the incline, and a negative
+/// speed runs the escalator downwards.
+#[derive(Component, Clone)]
+pub struct EscalatorProperties {
+    name: String,
+    step_count: u8,
+    rise: f32,
+    run: f32,
+    width: f32,
+    speed: f32,
+}
+
+impl Default for EscalatorProperties {
+    fn default() -> Self {
+        EscalatorProperties {
+            name: String::default(),
+            step_count: 0,
+            rise: 0.0,
+            run: 0.0,
+            width: 0.0,
+            speed: 0.0,
+        }
+    }
+}
+
+impl EscalatorProperties {
+    /// Create a new escalator properties component.
+    pub fn new(name: String, step_count: u8, rise: f32, run: f32, width: f32, speed: f32) -> Self {
+        EscalatorProperties {
+            name,
+            step_count,
+            rise,
+            run,
+            width,
+            speed,
+        }
+    }
+
+    /// Get the escalator name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the number of steps.
+    pub fn step_count(&self) -> u8 {
+        self.step_count
+    }
+
+    /// Get the escalator speed.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Get the height the escalator climbs over all of its steps.
+    pub fn total_rise(&self) -> f32 {
+        self.rise * self.step_count as f32
+    }
+
+    /// Get the distance the escalator covers over all of its steps.
+    pub fn total_run(&self) -> f32 {
+        self.run * self.step_count as f32
+    }
+
+    /// Get the local position of a step that is `position` steps from the bottom.
+    fn step_translation(&self, position: f32) -> Vec3 {
+        Vec3::new(
+            self.run * (position + 0.5),
+            self.rise * (position + 0.5),
+            self.width / 2.0,
+        )
+    }
+}
+
+/// A component to store whether the escalator is running.
+#[derive(Component, Clone, Copy, PartialEq, Debug, Default)]
+pub enum EscalatorState {
+    #[default]
+    Moving,
+    Stopped,
+}
+
+/// A component to mark a step of an escalator.
+///
+/// The position is the number of steps from the bottom of the escalator, and wraps around once
+/// the step goes past either end.
+#[derive(Component)]
+pub struct EscalatorStep {
+    position: f32,
+}
+
+impl EscalatorStep {
+    /// Get the number of steps from the bottom of the escalator.
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+}
+
+/// A Bevy plugin for escalators.
+pub struct BevyEscalatorPlugin;
+
+impl Plugin for BevyEscalatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EscalatorEvent>();
+        app.add_systems(Update, spawn_escalator);
+        app.add_systems(
+            Update,
+            update_escalator_state.before(update_escalator_movement),
+        );
+        app.add_systems(Update, update_escalator_movement);
+    }
+}
+
+/// A system to spawn escalators.
+///
+/// The condition for spawning escalators is when the escalator properties are added.
+fn spawn_escalator(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    queries: Query<(Entity, &EscalatorProperties), Added<EscalatorProperties>>,
+) {
+    for (entity, properties) in queries.iter() {
+        if properties.step_count == 0 {
+            log::warn!("Escalator {} has no steps", properties.name);
+            continue;
+        }
+
+        let material = materials.add(DEFAULT_ESCALATOR_COLOR);
+        let step_mesh = meshes.add(Cuboid::new(
+            properties.run,
+            properties.rise,
+            properties.width,
+        ));
+
+        for step in 0..properties.step_count {
+            let position = step as f32;
+            let step_entity = commands
+                .spawn(PbrBundle {
+                    mesh: step_mesh.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_translation(properties.step_translation(position)),
+                    ..default()
+                })
+                .insert(EscalatorStep { position })
+                .id();
+            commands.entity(entity).add_child(step_entity);
+        }
+    }
+}
+
+/// A system to start and stop escalators based on the escalator event.
+fn update_escalator_state(
+    mut escalator_requests: EventReader<EscalatorEvent>,
+    mut queries: Query<(&EscalatorProperties, &mut EscalatorState)>,
+) {
+    for escalator_request in escalator_requests.read() {
+        for (properties, mut state) in queries.iter_mut() {
+            if properties.name != escalator_request.name {
+                continue;
+            }
+
+            if *state != escalator_request.state {
+                log::info!(
+                    "Escalator {} is now {:?}",
+                    properties.name,
+                    escalator_request.state
+                );
+                *state = escalator_request.state;
+            }
+        }
+    }
+}
+
+/// A system to move the steps of running escalators.
+///
+/// Each step moves along the incline, and a step that leaves the top of the escalator comes back
+/// in at the bottom, or the other way around when running downwards.
+fn update_escalator_movement(
+    time: Res<Time>,
+    escalator_queries: Query<(&EscalatorProperties, &EscalatorState)>,
+    mut step_queries: Query<(&Parent, &mut Transform, &mut EscalatorStep)>,
+) {
+    for (parent, mut transform, mut step) in step_queries.iter_mut() {
+        let (properties, state) = escalator_queries
+            .get(parent.get())
+            .expect("Escalator properties not found");
+
+        if *state == EscalatorState::Stopped {
+            continue;
+        }
+
+        let step_length = properties.rise.hypot(properties.run);
+        if step_length == 0.0 {
+            continue;
+        }
+
+        let step_count = properties.step_count as f32;
+        step.position = (step.position + properties.speed * time.delta_seconds() / step_length)
+            .rem_euclid(step_count);
+        transform.translation = properties.step_translation(step.position);
+    }
+}
@@ -16,17 +16,120 @@
  *
 */
 // =========================================================================
+// Bevy systems take their resources and queries as arguments, so they often have many of them
+// with long query types.
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
 use bevy::prelude::*;
 mod door;
+mod escalator;
 mod lift;
+mod stair;
+mod wall;
+mod window;
 
 #[doc(hidden)]
-pub use crate::{door::*, lift::*};
+pub use crate::{door::*, escalator::*, lift::*, stair::*, wall::*, window::*};
 
-pub struct BevyInfrastructurePlugin;
+/// A Bevy plugin for every infrastructure module, or only the ones chosen with its `with_*`
+/// methods.
+///
+/// A plugin with no module chosen adds all of them with their default configuration. This keeps
+/// `BevyInfrastructurePlugin::default()` adding every module, as it did before modules could be
+/// chosen.
+#[derive(Default)]
+pub struct BevyInfrastructurePlugin {
+    doors: Option<DoorPluginConfig>,
+    door_plugin: Option<door::BevyDoorPlugin>,
+    lifts: Option<LiftPluginConfig>,
+    escalators: bool,
+    stairs: bool,
+    walls: bool,
+    windows: bool,
+}
+
+impl BevyInfrastructurePlugin {
+    /// Add the door plugin with the given configuration.
+    pub fn with_doors(mut self, config: DoorPluginConfig) -> Self {
+        self.doors = Some(config);
+        self
+    }
+
+    /// Add the door plugin itself, for example to set its `strict_mode`.
+    ///
+    /// The door configuration is still chosen with `with_doors`, or left at its default.
+    pub fn with_door_plugin(mut self, plugin: door::BevyDoorPlugin) -> Self {
+        self.door_plugin = Some(plugin);
+        self
+    }
+
+    /// Add the lift plugin with the given configuration.
+    pub fn with_lifts(mut self, config: LiftPluginConfig) -> Self {
+        self.lifts = Some(config);
+        self
+    }
+
+    /// Add the escalator plugin.
+    pub fn with_escalators(mut self) -> Self {
+        self.escalators = true;
+        self
+    }
+
+    /// Add the stair plugin.
+    pub fn with_stairs(mut self) -> Self {
+        self.stairs = true;
+        self
+    }
+
+    /// Add the wall plugin.
+    pub fn with_walls(mut self) -> Self {
+        self.walls = true;
+        self
+    }
+
+    /// Add the window plugin.
+    pub fn with_windows(mut self) -> Self {
+        self.windows = true;
+        self
+    }
+
+    /// Whether no module was chosen.
+    fn is_empty(&self) -> bool {
+        self.doors.is_none()
+            && self.door_plugin.is_none()
+            && self.lifts.is_none()
+            && !self.escalators
+            && !self.stairs
+            && !self.walls
+            && !self.windows
+    }
+}
 
 impl Plugin for BevyInfrastructurePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(door::BevyDoorPlugin);
+        let all = self.is_empty();
+        if let Some(config) = &self.doors {
+            app.insert_resource(config.clone());
+        }
+        if all || self.doors.is_some() || self.door_plugin.is_some() {
+            app.add_plugins(self.door_plugin.clone().unwrap_or_default());
+        }
+        if all || self.escalators {
+            app.add_plugins(escalator::BevyEscalatorPlugin);
+        }
+        if let Some(config) = &self.lifts {
+            app.insert_resource(config.clone());
+        }
+        if all || self.lifts.is_some() {
+            app.add_plugins(lift::BevyLiftPlugin);
+        }
+        if all || self.stairs {
+            app.add_plugins(stair::BevyStairPlugin);
+        }
+        if all || self.walls {
+            app.add_plugins(wall::BevyWallPlugin);
+        }
+        if all || self.windows {
+            app.add_plugins(window::BevyWindowPlugin);
+        }
     }
 }
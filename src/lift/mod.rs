@@ -17,3 +17,382 @@
 */
 // =========================================================================
 use super::*;
+
+/// A Bevy event for lift actions.
+#[derive(Event)]
+pub struct LiftEvent {
+    name: String,
+    floor: usize,
+}
+
+impl LiftEvent {
+    /// Call the lift to a floor, by index into the lift's floor list.
+    pub fn call(name: String, floor: usize) -> Self {
+        LiftEvent { name, floor }
+    }
+
+    /// Send the lift to a floor, by index into the lift's floor list.
+    pub fn go_to(name: String, floor: usize) -> Self {
+        LiftEvent::call(name, floor)
+    }
+}
+
+/// A component bundle for lifts.
+#[derive(Bundle, Default)]
+pub struct LiftBundle {
+    pub lift_properties: LiftProperties,
+    pub lift_dimensions: LiftDimensions,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub inherited_visibility: InheritedVisibility,
+    pub view_visibility: ViewVisibility,
+}
+
+/// The default lift speed, in units per second.
+pub const DEFAULT_LIFT_SPEED: f32 = 1.0;
+
+/// The default number of seconds a lift holds its doors open after arriving at a floor.
+pub const DEFAULT_LIFT_DOOR_HOLD_SECONDS: f32 = 3.0;
+
+/// A component to store lift properties.
+#[derive(Component)]
+pub struct LiftProperties {
+    name: String,
+    floors: Vec<f32>,
+    speed: f32,
+    door_hold_seconds: Option<f32>,
+    current_floor: usize,
+    doors_open_seconds: f32,
+}
+
+impl Default for LiftProperties {
+    fn default() -> Self {
+        LiftProperties {
+            name: String::default(),
+            floors: vec![0.0],
+            speed: DEFAULT_LIFT_SPEED,
+            door_hold_seconds: None,
+            current_floor: 0,
+            doors_open_seconds: 0.0,
+        }
+    }
+}
+
+impl LiftProperties {
+    /// Create a new lift properties component.
+    ///
+    /// The floors are the heights of each floor relative to the lift's transform, and the speed
+    /// is in units per second.
+    pub fn new(name: String, floors: Vec<f32>, speed: f32) -> Self {
+        LiftProperties {
+            name,
+            floors,
+            speed: speed.abs(),
+            door_hold_seconds: None,
+            current_floor: 0,
+            doors_open_seconds: 0.0,
+        }
+    }
+
+    /// Set the number of seconds the lift holds its doors open after arriving at a floor.
+    pub fn with_door_hold_seconds(mut self, door_hold_seconds: f32) -> Self {
+        self.door_hold_seconds = Some(door_hold_seconds.max(0.0));
+        self
+    }
+
+    /// Get the lift name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the heights of the lift's floors, relative to the lift's transform.
+    pub fn floors(&self) -> &[f32] {
+        &self.floors
+    }
+
+    /// Get the lift speed, in units per second.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Get the number of seconds the lift holds its doors open after arriving at a floor.
+    ///
+    /// A lift without a hold time set with `with_door_hold_seconds` holds its doors for the
+    /// `default_door_hold_seconds` of the `LiftPluginConfig` once spawned, and reports
+    /// `DEFAULT_LIFT_DOOR_HOLD_SECONDS` before.
+    pub fn door_hold_seconds(&self) -> f32 {
+        self.door_hold_seconds
+            .unwrap_or(DEFAULT_LIFT_DOOR_HOLD_SECONDS)
+    }
+
+    /// Get the floor the lift last arrived at, by index into the lift's floor list.
+    pub fn current_floor(&self) -> usize {
+        self.current_floor
+    }
+}
+
+/// A component to store lift dimensions.
+#[derive(Component, Default)]
+pub struct LiftDimensions {
+    length: f32,
+    width: f32,
+    thickness: f32,
+}
+
+impl LiftDimensions {
+    /// Create a new lift dimensions component.
+    pub fn new(length: f32, width: f32, thickness: f32) -> Self {
+        LiftDimensions {
+            length,
+            width,
+            thickness,
+        }
+    }
+}
+
+/// A component to store the lift's current state.
+///
+/// A lift arriving at a floor is `DoorsOpen` for `door_hold_seconds`, and then `Idle` until it is
+/// called to another floor.
+#[derive(Component, Clone, Copy, PartialEq, Default)]
+pub enum LiftState {
+    #[default]
+    Idle,
+    Moving,
+    DoorsOpen,
+}
+
+/// A component to store the lift's goal floor, by index into the lift's floor list.
+#[derive(Component, Clone, Copy, PartialEq, Default)]
+pub struct LiftGoal(pub usize);
+
+#[derive(Component)]
+pub struct LiftPlatform;
+
+/// A component to mark doors that open and close with a lift.
+///
+/// Add it next to the `DoorBundle` of a door spawned as a child of the lift entity, or of its
+/// platform for doors that travel with it. The doors open when the lift arrives at a floor and
+/// close when it stops holding them open, and the lift does not leave until they are closed.
+#[derive(Component)]
+pub struct LiftDoor;
+
+/// A resource to configure the lift plugin.
+#[derive(Resource, Clone)]
+pub struct LiftPluginConfig {
+    /// The door hold time of lifts without a hold time of their own, in seconds.
+    pub default_door_hold_seconds: f32,
+}
+
+impl Default for LiftPluginConfig {
+    fn default() -> Self {
+        LiftPluginConfig {
+            default_door_hold_seconds: DEFAULT_LIFT_DOOR_HOLD_SECONDS,
+        }
+    }
+}
+
+/// A Bevy plugin for lifts.
+///
+/// It works without the door plugin, lift doors are only opened and closed once the door plugin
+/// is added too.
+pub struct BevyLiftPlugin;
+
+impl Plugin for BevyLiftPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LiftPluginConfig>();
+        app.add_event::<LiftEvent>();
+        // The lift doors send door events, which the door plugin may not have added
+        app.add_event::<DoorEvent>();
+        app.add_systems(Update, spawn_lift);
+        app.add_systems(Update, update_lift_goal);
+        app.add_systems(Update, update_lift_movement);
+        app.add_systems(
+            Update,
+            update_lift_doors
+                .in_set(DoorSystemSet::Request)
+                .after(update_lift_movement)
+                .run_if(resource_exists::<DoorRegistry>),
+        );
+    }
+}
+
+/// A system to spawn lifts.
+///
+/// The condition for spawning lifts is when the lift properties are added.
+fn spawn_lift(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<LiftPluginConfig>,
+    mut queries: Query<(Entity, &mut LiftProperties, &LiftDimensions), Added<LiftProperties>>,
+) {
+    for (entity, mut properties, dimensions) in queries.iter_mut() {
+        if properties.door_hold_seconds.is_none() {
+            properties.door_hold_seconds = Some(config.default_door_hold_seconds.max(0.0));
+        }
+        let Some(ground_floor) = properties.floors.first() else {
+            log::warn!("Lift {} has no floors", properties.name);
+            continue;
+        };
+
+        // The platform's top surface sits at the floor height
+        let platform_mesh = commands
+            .spawn(PbrBundle {
+                mesh: meshes.add(Cuboid::new(
+                    dimensions.length,
+                    dimensions.thickness,
+                    dimensions.width,
+                )),
+                material: materials.add(Color::srgb_u8(160, 160, 160)),
+                transform: Transform::from_xyz(
+                    dimensions.length / 2.0,
+                    -dimensions.thickness / 2.0,
+                    dimensions.width / 2.0,
+                ),
+                ..default()
+            })
+            .id();
+
+        let platform = commands
+            .spawn(PbrBundle {
+                transform: Transform::from_xyz(0.0, *ground_floor, 0.0),
+                ..default()
+            })
+            .id();
+
+        commands.entity(platform).add_child(platform_mesh);
+        commands.entity(platform).insert(LiftPlatform);
+        commands.entity(platform).insert(LiftState::default());
+        commands.entity(platform).insert(LiftGoal::default());
+        commands.entity(entity).add_child(platform);
+    }
+}
+
+/// A system to update the lift goal based on the lift event.
+fn update_lift_goal(
+    mut lift_requests: EventReader<LiftEvent>,
+    lift_property_queries: Query<&LiftProperties>,
+    mut lift_goal_queries: Query<(&Parent, &mut LiftGoal), With<LiftPlatform>>,
+) {
+    for lift_request in lift_requests.read() {
+        for (parent, mut goal) in lift_goal_queries.iter_mut() {
+            let properties = lift_property_queries
+                .get(parent.get())
+                .expect("Lift properties not found");
+
+            if properties.name != lift_request.name {
+                continue;
+            }
+
+            if lift_request.floor >= properties.floors.len() {
+                log::warn!(
+                    "Lift {} has no floor {}",
+                    properties.name,
+                    lift_request.floor
+                );
+                continue;
+            }
+
+            if goal.0 != lift_request.floor {
+                log::info!(
+                    "Calling lift {} to floor {}",
+                    properties.name,
+                    lift_request.floor
+                );
+                goal.0 = lift_request.floor;
+            }
+        }
+    }
+}
+
+/// A system to move the lift platform towards its goal floor.
+///
+/// The lift moves at `speed` units per second and snaps onto the floor once it is within a
+/// single step of it. On arrival the lift opens its doors and records its current floor. It holds
+/// its doors open for `door_hold_seconds`, and only leaves once its `LiftDoor`s are closed.
+fn update_lift_movement(
+    time: Res<Time>,
+    mut lift_property_queries: Query<&mut LiftProperties>,
+    children_queries: Query<&Children>,
+    lift_door_queries: Query<(Entity, &Parent), With<LiftDoor>>,
+    joint_queries: Query<&DoorState, With<DoorJoint>>,
+    mut queries: Query<
+        (Entity, &Parent, &mut Transform, &mut LiftState, &LiftGoal),
+        With<LiftPlatform>,
+    >,
+) {
+    for (platform, parent, mut transform, mut state, goal) in queries.iter_mut() {
+        let lift = parent.get();
+        let mut properties = lift_property_queries
+            .get_mut(lift)
+            .expect("Lift properties not found");
+
+        let Some(&target) = properties.floors.get(goal.0) else {
+            continue;
+        };
+
+        if *state == LiftState::DoorsOpen {
+            properties.doors_open_seconds += time.delta_seconds();
+            if properties.doors_open_seconds >= properties.door_hold_seconds() {
+                properties.doors_open_seconds = 0.0;
+                *state = LiftState::Idle;
+            }
+            continue;
+        }
+
+        let step = properties.speed * time.delta_seconds();
+        let remaining = target - transform.translation.y;
+
+        if remaining.abs() <= step.max(f32::EPSILON) {
+            transform.translation.y = target;
+            if *state == LiftState::Moving {
+                log::info!("Lift {} arrived at floor {}", properties.name, goal.0);
+                properties.current_floor = goal.0;
+                *state = LiftState::DoorsOpen;
+            }
+        } else {
+            // Wait for the lift doors to close before leaving the floor
+            let doors_closed = lift_door_queries
+                .iter()
+                .filter(|(_, door_parent)| {
+                    door_parent.get() == lift || door_parent.get() == platform
+                })
+                .flat_map(|(door, _)| {
+                    joint_queries.iter_many(children_queries.iter_descendants(door))
+                })
+                .all(|state| *state == DoorState::Closed);
+            if *state != LiftState::Moving && !doors_closed {
+                continue;
+            }
+
+            transform.translation.y += step * remaining.signum();
+            if *state != LiftState::Moving {
+                *state = LiftState::Moving;
+            }
+        }
+    }
+}
+
+/// A system to open and close the `LiftDoor`s of a lift as its state changes.
+fn update_lift_doors(
+    mut door_requests: EventWriter<DoorEvent>,
+    lift_door_queries: Query<(Entity, &Parent), With<LiftDoor>>,
+    queries: Query<(Entity, &Parent, &LiftState), (With<LiftPlatform>, Changed<LiftState>)>,
+) {
+    for (platform, parent, state) in queries.iter() {
+        let lift = parent.get();
+        for (door, door_parent) in lift_door_queries.iter() {
+            if door_parent.get() != lift && door_parent.get() != platform {
+                continue;
+            }
+            match state {
+                LiftState::DoorsOpen => door_requests.send(DoorEvent::open_entity(door)),
+                LiftState::Idle | LiftState::Moving => {
+                    door_requests.send(DoorEvent::close_entity(door))
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,237 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The default stair color.
+pub const DEFAULT_STAIR_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+/// The default height of a stair railing above the steps.
+pub const DEFAULT_STAIR_RAILING_HEIGHT: f32 = 0.9;
+
+/// The thickness of the rails and posts of a stair railing.
+const STAIR_RAILING_THICKNESS: f32 = 0.04;
+
+/// A component bundle for stairs.
+#[derive(Bundle, Default)]
+pub struct StairBundle {
+    pub stair_properties: StairProperties,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub inherited_visibility: InheritedVisibility,
+    pub view_visibility: ViewVisibility,
+}
+
+/// A component to store stair properties.
+///
+/// The stair climbs along its local X axis from its transform, which is the lower corner of the
+/// first step, and spans its width along the Z axis. Each step rises by `rise` and runs by `run`,
+/// so a stair reaches the floor above when `rise * step_count` equals the floor height.
+#[derive(Component, Clone)]
+pub struct StairProperties {
+    name: String,
+    step_count: u8,
+    rise: f32,
+    run: f32,
+    width: f32,
+}
+
+impl Default for StairProperties {
+    fn default() -> Self {
+        StairProperties {
+            name: String::default(),
+            step_count: 0,
+            rise: 0.0,
+            run: 0.0,
+            width: 0.0,
+        }
+    }
+}
+
+impl StairProperties {
+    /// Create a new stair properties component.
+    pub fn new(name: String, step_count: u8, rise: f32, run: f32, width: f32) -> Self {
+        StairProperties {
+            name,
+            step_count,
+            rise,
+            run,
+            width,
+        }
+    }
+
+    /// Get the stair name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the number of steps.
+    pub fn step_count(&self) -> u8 {
+        self.step_count
+    }
+
+    /// Get the height the stair climbs over all of its steps.
+    pub fn total_rise(&self) -> f32 {
+        self.rise * self.step_count as f32
+    }
+
+    /// Get the distance the stair covers over all of its steps.
+    pub fn total_run(&self) -> f32 {
+        self.run * self.step_count as f32
+    }
+}
+
+/// A component to add handrails to both sides of a stair.
+///
+/// Add it to a stair entity next to the `StairBundle`.
+#[derive(Component, Clone)]
+pub struct StairRailing {
+    pub height: f32,
+}
+
+impl Default for StairRailing {
+    fn default() -> Self {
+        StairRailing {
+            height: DEFAULT_STAIR_RAILING_HEIGHT,
+        }
+    }
+}
+
+impl StairRailing {
+    /// Create a new stair railing component.
+    pub fn new(height: f32) -> Self {
+        StairRailing { height }
+    }
+}
+
+/// A Bevy plugin for stairs.
+pub struct BevyStairPlugin;
+
+impl Plugin for BevyStairPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, spawn_stair);
+    }
+}
+
+/// A system to spawn stairs.
+///
+/// The condition for spawning stairs is when the stair properties are added.
+fn spawn_stair(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    queries: Query<(Entity, &StairProperties, Option<&StairRailing>), Added<StairProperties>>,
+) {
+    for (entity, properties, railing) in queries.iter() {
+        if properties.step_count == 0 {
+            log::warn!("Stair {} has no steps", properties.name);
+            continue;
+        }
+
+        let material = materials.add(DEFAULT_STAIR_COLOR);
+        let step_mesh = meshes.add(Cuboid::new(
+            properties.run,
+            properties.rise,
+            properties.width,
+        ));
+
+        for step in 0..properties.step_count {
+            let step_entity = commands
+                .spawn(PbrBundle {
+                    mesh: step_mesh.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_xyz(
+                        properties.run * (step as f32 + 0.5),
+                        properties.rise * (step as f32 + 0.5),
+                        properties.width / 2.0,
+                    ),
+                    ..default()
+                })
+                .id();
+            commands.entity(entity).add_child(step_entity);
+        }
+
+        if let Some(railing) = railing {
+            spawn_stair_railing(
+                &mut commands,
+                &mut meshes,
+                material,
+                properties,
+                railing,
+                entity,
+            );
+        }
+    }
+}
+
+/// Spawn a handrail on each side of a stair, above the nose of every step.
+fn spawn_stair_railing(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    properties: &StairProperties,
+    railing: &StairRailing,
+    stair: Entity,
+) {
+    let slope = properties.total_rise().atan2(properties.total_run());
+    let rail_length = properties.total_rise().hypot(properties.total_run());
+    let rail_mesh = meshes.add(Cuboid::new(
+        rail_length,
+        STAIR_RAILING_THICKNESS,
+        STAIR_RAILING_THICKNESS,
+    ));
+    let post_mesh = meshes.add(Cuboid::new(
+        STAIR_RAILING_THICKNESS,
+        railing.height,
+        STAIR_RAILING_THICKNESS,
+    ));
+
+    for side in [0.0, properties.width] {
+        let rail = commands
+            .spawn(PbrBundle {
+                mesh: rail_mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_xyz(
+                    properties.total_run() / 2.0,
+                    properties.total_rise() / 2.0 + railing.height,
+                    side,
+                )
+                .with_rotation(Quat::from_rotation_z(slope)),
+                ..default()
+            })
+            .id();
+        commands.entity(stair).add_child(rail);
+
+        // A post at the bottom and top of each rail
+        for (x, y) in [
+            (0.0, 0.0),
+            (properties.total_run(), properties.total_rise()),
+        ] {
+            let post = commands
+                .spawn(PbrBundle {
+                    mesh: post_mesh.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_xyz(x, y + railing.height / 2.0, side),
+                    ..default()
+                })
+                .id();
+            commands.entity(stair).add_child(post);
+        }
+    }
+}
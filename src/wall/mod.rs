@@ -0,0 +1,219 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The default wall color.
+pub const DEFAULT_WALL_COLOR: Color = Color::srgb(0.85, 0.85, 0.8);
+
+/// A component bundle for walls.
+#[derive(Bundle, Default)]
+pub struct WallBundle {
+    pub wall_properties: WallProperties,
+    pub wall_dimensions: WallDimensions,
+    pub wall_material: WallMaterial,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub inherited_visibility: InheritedVisibility,
+    pub view_visibility: ViewVisibility,
+}
+
+/// A rectangular cutout in a wall, for a door or a window.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct WallOpening {
+    /// The distance from the start of the wall to the edge of the opening.
+    pub offset: f32,
+    pub width: f32,
+    pub height: f32,
+    /// The height of the bottom of the opening above the floor, `0.0` for doors.
+    pub elevation: f32,
+}
+
+impl WallOpening {
+    /// Create a new wall opening that starts at the floor.
+    pub fn new(offset: f32, width: f32, height: f32) -> Self {
+        WallOpening {
+            offset,
+            width,
+            height,
+            elevation: 0.0,
+        }
+    }
+
+    /// Raise the bottom of the opening above the floor, as for a window.
+    pub fn with_elevation(mut self, elevation: f32) -> Self {
+        self.elevation = elevation;
+        self
+    }
+}
+
+/// A component to store wall properties.
+#[derive(Component, Clone, Default)]
+pub struct WallProperties {
+    name: String,
+    openings: Vec<WallOpening>,
+}
+
+impl WallProperties {
+    /// Create a new wall properties component.
+    ///
+    /// The openings are cut out of the wall when it is spawned.
+    pub fn new(name: String, openings: Vec<WallOpening>) -> Self {
+        WallProperties { name, openings }
+    }
+
+    /// Get the wall name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the openings cut out of the wall.
+    pub fn openings(&self) -> &[WallOpening] {
+        &self.openings
+    }
+}
+
+/// A component to store wall dimensions.
+///
+/// The wall runs along its local X axis from its transform, which is the lower corner of the wall
+/// like the anchor point of a door, and is centred on its thickness.
+#[derive(Component, Clone, Default)]
+pub struct WallDimensions {
+    length: f32,
+    height: f32,
+    thickness: f32,
+}
+
+impl WallDimensions {
+    /// Create a new wall dimensions component.
+    pub fn new(length: f32, height: f32, thickness: f32) -> Self {
+        WallDimensions {
+            length,
+            height,
+            thickness,
+        }
+    }
+}
+
+/// A component to set the color of a wall.
+#[derive(Component, Clone)]
+pub struct WallMaterial {
+    pub color: Color,
+}
+
+impl Default for WallMaterial {
+    fn default() -> Self {
+        WallMaterial {
+            color: DEFAULT_WALL_COLOR,
+        }
+    }
+}
+
+impl From<Color> for WallMaterial {
+    fn from(color: Color) -> Self {
+        WallMaterial { color }
+    }
+}
+
+/// A Bevy plugin for walls.
+pub struct BevyWallPlugin;
+
+impl Plugin for BevyWallPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, spawn_wall);
+    }
+}
+
+/// A system to spawn walls.
+///
+/// The condition for spawning walls is when the wall properties are added.
+fn spawn_wall(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    queries: Query<
+        (Entity, &WallProperties, &WallDimensions, &WallMaterial),
+        Added<WallProperties>,
+    >,
+) {
+    for (entity, properties, dimensions, material) in queries.iter() {
+        let wall = commands
+            .spawn(PbrBundle {
+                mesh: meshes.add(wall_mesh(properties, dimensions)),
+                material: materials.add(material.color),
+                ..default()
+            })
+            .id();
+        commands.entity(entity).add_child(wall);
+    }
+}
+
+/// Build the mesh of a wall with its openings cut out.
+///
+/// The wall is split into blocks around the openings: full height blocks between them, and a
+/// block above and below each opening.
+fn wall_mesh(properties: &WallProperties, dimensions: &WallDimensions) -> Mesh {
+    let mut openings: Vec<WallOpening> = properties.openings.clone();
+    openings.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+    let mut blocks: Vec<(Vec2, Vec2)> = Vec::new();
+    let mut cursor = 0.0;
+    for opening in openings {
+        let start = opening.offset;
+        let end = opening.offset + opening.width;
+        let bottom = opening.elevation.max(0.0);
+        let top = opening.elevation + opening.height;
+        if start < cursor || end > dimensions.length || opening.width <= 0.0 || bottom >= top {
+            log::warn!(
+                "Wall {} opening at {} does not fit in the wall, ignoring it",
+                properties.name,
+                opening.offset
+            );
+            continue;
+        }
+
+        blocks.push((Vec2::new(cursor, 0.0), Vec2::new(start, dimensions.height)));
+        blocks.push((Vec2::new(start, 0.0), Vec2::new(end, bottom)));
+        blocks.push((
+            Vec2::new(start, top.min(dimensions.height)),
+            Vec2::new(end, dimensions.height),
+        ));
+        cursor = end;
+    }
+    blocks.push((
+        Vec2::new(cursor, 0.0),
+        Vec2::new(dimensions.length, dimensions.height),
+    ));
+
+    let mut wall: Option<Mesh> = None;
+    for (min, max) in blocks {
+        let size = max - min;
+        if size.x <= 0.0 || size.y <= 0.0 {
+            continue;
+        }
+        let block = Mesh::from(Cuboid::new(size.x, size.y, dimensions.thickness))
+            .translated_by(((min + max) / 2.0).extend(0.0));
+        match wall.as_mut() {
+            Some(wall) => wall.merge(&block),
+            None => wall = Some(block),
+        }
+    }
+    // A wall without any block left is drawn as an empty cuboid
+    wall.unwrap_or_else(|| Mesh::from(Cuboid::new(0.0, 0.0, dimensions.thickness)))
+}
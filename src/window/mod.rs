@@ -0,0 +1,328 @@
+// =========================================================================
+/*
+ * Copyright (C) 2019 Tan Jun Kiat
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+// =========================================================================
+use super::*;
+
+/// The default color of window glass, mostly transparent.
+pub const DEFAULT_WINDOW_GLASS_COLOR: Color = Color::srgba(0.8, 0.9, 1.0, 0.3);
+
+/// The default window speed, in units or radians per second.
+pub const DEFAULT_WINDOW_SPEED: f32 = 0.5;
+
+/// The distance at which a moving window snaps onto its goal, in units or radians.
+const WINDOW_SNAP_TOLERANCE: f32 = 0.01;
+
+/// A Bevy event for window actions.
+#[derive(Event)]
+pub struct WindowEvent {
+    name: String,
+    goal: WindowGoal,
+}
+
+impl WindowEvent {
+    pub fn open(name: String) -> Self {
+        WindowEvent {
+            name,
+            goal: WindowGoal::Open,
+        }
+    }
+
+    pub fn close(name: String) -> Self {
+        WindowEvent {
+            name,
+            goal: WindowGoal::Closed,
+        }
+    }
+}
+
+/// A component bundle for windows.
+#[derive(Bundle, Default)]
+pub struct WindowBundle {
+    pub window_properties: WindowProperties,
+    pub window_dimensions: WindowDimensions,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub inherited_visibility: InheritedVisibility,
+    pub view_visibility: ViewVisibility,
+}
+
+/// A enum to describe the window type.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum WindowType {
+    /// Swings about a vertical hinge on its left edge.
+    #[default]
+    Casement,
+    /// Swings about a horizontal hinge on its top edge.
+    Awning,
+    /// Never opens.
+    Fixed,
+    /// Slides along its length.
+    Sliding,
+}
+
+/// A component to store window properties.
+#[derive(Component, Clone)]
+pub struct WindowProperties {
+    name: String,
+    open_angle: f32,
+    window_type: WindowType,
+    speed: f32,
+}
+
+impl Default for WindowProperties {
+    fn default() -> Self {
+        WindowProperties {
+            name: String::default(),
+            open_angle: 0.0,
+            window_type: WindowType::default(),
+            speed: DEFAULT_WINDOW_SPEED,
+        }
+    }
+}
+
+impl WindowProperties {
+    /// Create a new window properties component.
+    ///
+    /// The open angle is in radians for casement and awning windows, with its sign picking the
+    /// side they open to, and the distance to slide in units for sliding windows.
+    pub fn new(name: String, open_angle: f32, window_type: WindowType) -> Self {
+        WindowProperties {
+            name,
+            open_angle,
+            window_type,
+            speed: DEFAULT_WINDOW_SPEED,
+        }
+    }
+
+    /// Set the window speed, in units per second for sliding windows and radians per second for
+    /// the others.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed.abs();
+        self
+    }
+
+    /// Get the window name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the window open angle.
+    pub fn open_angle(&self) -> f32 {
+        self.open_angle
+    }
+
+    /// Get the window type.
+    pub fn window_type(&self) -> WindowType {
+        self.window_type
+    }
+}
+
+/// A component to store window dimensions.
+///
+/// The window's transform is its lower corner, like the anchor point of a door.
+#[derive(Component, Clone, Default)]
+pub struct WindowDimensions {
+    length: f32,
+    height: f32,
+    thickness: f32,
+}
+
+impl WindowDimensions {
+    /// Create a new window dimensions component.
+    pub fn new(length: f32, height: f32, thickness: f32) -> Self {
+        WindowDimensions {
+            length,
+            height,
+            thickness,
+        }
+    }
+}
+
+/// A component to store the window's current state.
+#[derive(Component, Clone, Copy, PartialEq, Debug, Default)]
+pub enum WindowState {
+    Open,
+    #[default]
+    Closed,
+    Opening,
+    Closing,
+}
+
+/// A component to store the window's goal state.
+#[derive(Component, Clone, Copy, PartialEq, Debug, Default)]
+pub enum WindowGoal {
+    Open,
+    #[default]
+    Closed,
+}
+
+#[derive(Component)]
+pub struct WindowJoint;
+
+/// A Bevy plugin for windows.
+pub struct BevyWindowPlugin;
+
+impl Plugin for BevyWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<WindowEvent>();
+        app.add_systems(Update, spawn_window);
+        app.add_systems(Update, update_window_goal.before(update_window_movement));
+        app.add_systems(Update, update_window_movement);
+    }
+}
+
+/// A system to spawn windows.
+///
+/// The condition for spawning windows is when the window properties are added.
+fn spawn_window(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    queries: Query<(Entity, &WindowProperties, &WindowDimensions), Added<WindowProperties>>,
+) {
+    for (entity, properties, dimensions) in queries.iter() {
+        // Awning windows hang from a hinge along their top edge
+        let (joint_position, pane_position) = match properties.window_type {
+            WindowType::Awning => (
+                Vec3::new(0.0, dimensions.height, 0.0),
+                Vec3::new(dimensions.length / 2.0, -dimensions.height / 2.0, 0.0),
+            ),
+            WindowType::Casement | WindowType::Fixed | WindowType::Sliding => (
+                Vec3::ZERO,
+                Vec3::new(dimensions.length / 2.0, dimensions.height / 2.0, 0.0),
+            ),
+        };
+
+        let pane = commands
+            .spawn(PbrBundle {
+                mesh: meshes.add(Cuboid::new(
+                    dimensions.length,
+                    dimensions.height,
+                    dimensions.thickness,
+                )),
+                material: materials.add(StandardMaterial {
+                    base_color: DEFAULT_WINDOW_GLASS_COLOR,
+                    alpha_mode: AlphaMode::Blend,
+                    ..default()
+                }),
+                transform: Transform::from_translation(pane_position),
+                ..default()
+            })
+            .id();
+
+        let joint = commands
+            .spawn(PbrBundle {
+                transform: Transform::from_translation(joint_position),
+                ..default()
+            })
+            .id();
+
+        commands.entity(joint).add_child(pane);
+        commands.entity(joint).insert(WindowJoint);
+        commands.entity(joint).insert(WindowState::default());
+        commands.entity(joint).insert(WindowGoal::default());
+        commands.entity(entity).add_child(joint);
+    }
+}
+
+/// A system to update the window goal based on the window event.
+///
+/// A moving window turns around from where it is, and fixed windows ignore every event.
+fn update_window_goal(
+    mut window_requests: EventReader<WindowEvent>,
+    window_property_queries: Query<&WindowProperties>,
+    mut window_goal_queries: Query<(&Parent, &mut WindowGoal), With<WindowJoint>>,
+) {
+    for window_request in window_requests.read() {
+        for (parent, mut goal) in window_goal_queries.iter_mut() {
+            let properties = window_property_queries
+                .get(parent.get())
+                .expect("Window properties not found");
+
+            if properties.name != window_request.name {
+                continue;
+            }
+
+            if properties.window_type == WindowType::Fixed {
+                debug!("Window {} is fixed", properties.name);
+                continue;
+            }
+
+            if *goal != window_request.goal {
+                log::info!(
+                    "Moving window {} to {:?}",
+                    properties.name,
+                    window_request.goal
+                );
+                *goal = window_request.goal;
+            }
+        }
+    }
+}
+
+/// A system to move windows towards their goal.
+///
+/// Windows move at `speed` units or radians per second and snap onto their goal once they are
+/// within a single step of it.
+fn update_window_movement(
+    time: Res<Time>,
+    window_property_queries: Query<&WindowProperties>,
+    mut queries: Query<(&Parent, &mut Transform, &mut WindowState, &WindowGoal), With<WindowJoint>>,
+) {
+    for (parent, mut transform, mut state, goal) in queries.iter_mut() {
+        let properties = window_property_queries
+            .get(parent.get())
+            .expect("Window properties not found");
+
+        let position = match properties.window_type {
+            WindowType::Casement => transform.rotation.to_euler(EulerRot::YXZ).0,
+            WindowType::Awning => transform.rotation.to_euler(EulerRot::XYZ).0,
+            WindowType::Sliding => transform.translation.x,
+            WindowType::Fixed => continue,
+        };
+        let target = match goal {
+            WindowGoal::Open => properties.open_angle,
+            WindowGoal::Closed => 0.0,
+        };
+
+        let step = properties.speed * time.delta_seconds();
+        let remaining = target - position;
+        let (new_position, new_state) = if remaining.abs() <= step.max(WINDOW_SNAP_TOLERANCE) {
+            let resting_state = match goal {
+                WindowGoal::Open => WindowState::Open,
+                WindowGoal::Closed => WindowState::Closed,
+            };
+            (target, resting_state)
+        } else if (remaining > 0.0) == (properties.open_angle > 0.0) {
+            (position + step * remaining.signum(), WindowState::Opening)
+        } else {
+            (position + step * remaining.signum(), WindowState::Closing)
+        };
+
+        match properties.window_type {
+            WindowType::Casement => transform.rotation = Quat::from_rotation_y(new_position),
+            WindowType::Awning => transform.rotation = Quat::from_rotation_x(new_position),
+            WindowType::Sliding => transform.translation.x = new_position,
+            WindowType::Fixed => {}
+        }
+        if *state != new_state {
+            *state = new_state;
+        }
+    }
+}